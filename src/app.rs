@@ -1,7 +1,33 @@
+use std::path::PathBuf;
+
+use crate::{project::Location, references::Target};
+
 struct Instance {
     name: String,
+    path: PathBuf,
     raw: lcf::raw::RawLcf,
     converted: Result<lcf::Lcf, lcf::LcfReadError>,
+    /// The file as it was read, and where the nodes of the raw tree are in it.
+    bytes: Vec<u8>,
+    spans: Vec<crate::views::raw::offsets::Span>,
+}
+
+impl Instance {
+    /// Reads the file again after it was written, so the hex view shows what is on disk. When
+    /// that fails, the old bytes are no longer linked to the tree.
+    fn reload_bytes(&mut self) -> Result<(), String> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => {
+                self.spans = crate::views::raw::offsets::spans(&bytes, &self.raw);
+                self.bytes = bytes;
+                Ok(())
+            }
+            Err(err) => {
+                self.spans.clear();
+                Err(format!("Failed to read {}: {err}", self.path.display()))
+            }
+        }
+    }
 }
 
 pub struct App {
@@ -9,6 +35,25 @@ pub struct App {
     instances: Vec<Instance>,
     encoding: crate::code_page::CodePage,
     using_raw: bool,
+    project: Option<crate::project::Project>,
+    references: Option<Target>,
+    report: Option<crate::report::Report>,
+    problems: Option<Vec<crate::lint::Problem>>,
+    graph: Option<(crate::graph::Graph, crate::graph::Layout)>,
+    assets: crate::assets::Assets,
+    map_view: Option<crate::views::map::MapView>,
+    chipset_view: Option<crate::views::chipset::ChipSetView>,
+    grid_view: Option<crate::views::grid::GridView>,
+    save_filters: crate::views::raw::save_data::Filters,
+    save_diff: Option<crate::save_diff::View>,
+    save_editor: Option<crate::save_editor::Editor>,
+    interpreter: bool,
+    hex_view: Option<crate::views::hex::HexView>,
+    /// Tree nodes to open and select on the next frame.
+    reveal: Option<Vec<u64>>,
+    /// The last thing that failed, until its window is closed.
+    error: Option<String>,
+    export_summary: Option<crate::export::Summary>,
 }
 
 impl App {
@@ -18,6 +63,198 @@ impl App {
             instances: Vec::new(),
             encoding: Default::default(),
             using_raw: false,
+            project: None,
+            references: None,
+            report: None,
+            problems: None,
+            graph: None,
+            reveal: None,
+            assets: Default::default(),
+            map_view: None,
+            chipset_view: None,
+            grid_view: None,
+            save_filters: Default::default(),
+            save_diff: None,
+            save_editor: None,
+            interpreter: false,
+            hex_view: None,
+            error: None,
+            export_summary: None,
+        }
+    }
+
+    /// Opens a file as a new tab, or selects its tab if it is already open.
+    fn open(&mut self, path: PathBuf) -> Result<usize, Box<dyn std::error::Error>> {
+        if let Some(index) = self
+            .instances
+            .iter()
+            .position(|instance| instance.path == path)
+        {
+            self.selected = Some(index);
+            return Ok(index);
+        }
+
+        let bytes = std::fs::read(&path)?;
+        let mut cursor = std::io::Cursor::new(bytes);
+        let lcf = lcf::raw::RawLcf::read(&mut cursor)?;
+        let bytes = cursor.into_inner();
+        self.instances.push(Instance {
+            name: path
+                .file_name()
+                .map_or_default(|name| name.to_string_lossy().into_owned()),
+            path,
+            converted: lcf.clone().try_into(),
+            spans: crate::views::raw::offsets::spans(&bytes, &lcf),
+            bytes,
+            raw: lcf,
+        });
+        self.selected = Some(self.instances.len() - 1);
+        Ok(self.instances.len() - 1)
+    }
+
+    /// Like [`Self::open`], showing why the file couldn't be opened.
+    fn open_or_report(&mut self, path: PathBuf) -> Option<usize> {
+        let name = path.display().to_string();
+        match self.open(path) {
+            Ok(index) => Some(index),
+            Err(err) => {
+                self.error = Some(format!("Failed to open {name}: {err}"));
+                None
+            }
+        }
+    }
+
+    /// Replaces the layers of an open map with those of a Tiled map, saving it to disk.
+    fn import_tiled(&mut self, index: usize, tmx: &std::path::Path) {
+        let instance = &mut self.instances[index];
+        let (lcf::raw::RawLcf::RawMapUnit(raw), Ok(lcf::Lcf::MapUnit(map_unit))) =
+            (&instance.raw, &instance.converted)
+        else {
+            return;
+        };
+
+        let mut raw = raw.clone();
+        if let Err(err) = crate::tiled::import_file(tmx, &instance.path, &mut raw, map_unit) {
+            self.error = Some(format!("Failed to import {}: {err}", tmx.display()));
+            return;
+        }
+        instance.raw = lcf::raw::RawLcf::RawMapUnit(raw);
+        instance.converted = instance.raw.clone().try_into();
+        if let Err(err) = instance.reload_bytes() {
+            self.error = Some(err);
+        }
+
+        if let (Some(project), Ok(lcf::Lcf::MapUnit(map_unit))) =
+            (&mut self.project, &instance.converted)
+            && let Some(map) = project
+                .maps
+                .iter_mut()
+                .find(|map| map.path == instance.path)
+        {
+            map.unit = map_unit.clone();
+        }
+        if let Some(view) = &mut self.map_view {
+            view.invalidate();
+        }
+    }
+
+    /// Opens the map the party of a save stands on, marking it and the vehicles on that map.
+    fn show_save_location(&mut self, index: usize) {
+        let (Some(project), lcf::raw::RawLcf::RawSaveData(save)) =
+            (&self.project, &self.instances[index].raw)
+        else {
+            return;
+        };
+        let positions = crate::save::positions(save);
+        let Some((_, party)) = positions
+            .iter()
+            .find(|(character, _)| *character == crate::save::Character::Party)
+        else {
+            self.error = Some("The save has no party location".to_string());
+            return;
+        };
+        let Some(path) = project.map(party.map).map(|map| map.path.clone()) else {
+            self.error = Some(format!("Map{:04} is not in the project", party.map));
+            return;
+        };
+        let map = party.map;
+
+        if self.open_or_report(path.clone()).is_none() {
+            return;
+        }
+        self.using_raw = false;
+        self.map_view.get_or_insert_default().markers = Some(crate::views::map::Markers {
+            path,
+            positions: positions
+                .into_iter()
+                .filter(|(_, position)| position.map == map)
+                .collect(),
+            reveal: true,
+        });
+    }
+
+    /// Opens the file containing `location` and reveals it in the tree.
+    fn goto(&mut self, location: Location) {
+        let Some(project) = &self.project else {
+            return;
+        };
+
+        match location {
+            Location::CommonEvent { id, command } => {
+                let path = project.database_path();
+                let Some(index) = self.open_or_report(path) else {
+                    return;
+                };
+                self.using_raw = true;
+                if let lcf::raw::RawLcf::RawDataBase(database) = &self.instances[index].raw {
+                    self.reveal =
+                        crate::views::raw::database::common_event_path(database, id, command);
+                }
+            }
+            Location::Event {
+                map,
+                event,
+                page,
+                command,
+            } => {
+                let Some(path) = project.map(map).map(|map| map.path.clone()) else {
+                    return;
+                };
+                let Some(index) = self.open_or_report(path) else {
+                    return;
+                };
+                self.using_raw = false;
+                if let Ok(lcf::Lcf::MapUnit(map_unit)) = &self.instances[index].converted {
+                    self.reveal =
+                        crate::views::map_unit::event_path(map_unit, event, page, command);
+                }
+            }
+            Location::Map { map } => {
+                // Opening a map that failed to read shows why.
+                let failed = project.failed_maps.iter().find(|failed| failed.id == map);
+                let Some(path) = project
+                    .map(map)
+                    .map(|map| map.path.clone())
+                    .or_else(|| failed.map(|failed| failed.path.clone()))
+                else {
+                    return;
+                };
+                if self.open_or_report(path).is_none() {
+                    return;
+                }
+                self.using_raw = false;
+                self.reveal = Some(vec![0]);
+            }
+            Location::MapTree { map } => {
+                let path = project.map_tree_path();
+                let Some(index) = self.open_or_report(path) else {
+                    return;
+                };
+                self.using_raw = true;
+                if let lcf::raw::RawLcf::RawMapTree(map_tree) = &self.instances[index].raw {
+                    self.reveal = crate::views::raw::map_tree::parent_path(map_tree, map);
+                }
+            }
         }
     }
 }
@@ -31,41 +268,143 @@ impl eframe::App for App {
                         .add_filter("Lcf file", &["ldb", "lmt", "lmu", "lsd"])
                         .pick_file()
                 {
-                    let bytes = std::fs::read(&path).unwrap();
-                    let mut cursor = std::io::Cursor::new(bytes);
-                    let lcf = lcf::raw::RawLcf::read(&mut cursor).unwrap();
-                    match &lcf {
-                        lcf::raw::RawLcf::RawDataBase(db) => {
-                            let items = &db.0.inner_vec;
-                            let terms = items
-                                .iter()
-                                .find_map(|item| match &item.data {
-                                    lcf::raw::ldb::LcfDataBaseChunk::States(x) => Some(x),
-                                    _ => None,
-                                })
-                                .unwrap();
-                            terms.inner_vec.iter().for_each(|chunk| match &chunk.data {
-                                lcf::helpers::UnknownChunk::Unknown { id, bytes }
-                                    if *id == 51 || *id == 52 || *id == 53 =>
-                                {
-                                    println!(
-                                        "{id}: {}",
-                                        self.encoding.to_encoding().decode(&bytes).0
-                                    );
-                                }
-                                _ => (),
-                            });
+                    self.open_or_report(path);
+                }
+
+                if ui.button("Open Project").clicked()
+                    && let Some(path) = rfd::FileDialog::new().pick_folder()
+                {
+                    match crate::project::Project::open(&path) {
+                        Ok(project) => {
+                            self.project = Some(project);
+                            self.report = None;
+                            self.problems = None;
+                            self.graph = None;
+                        }
+                        Err(err) => {
+                            self.error =
+                                Some(format!("Failed to open project {}: {err}", path.display()));
+                        }
+                    }
+                }
+
+                if ui
+                    .add_enabled(self.project.is_some(), egui::Button::new("Report"))
+                    .clicked()
+                    && let Some(project) = &self.project
+                {
+                    self.report = Some(crate::report::Report::build(project));
+                }
+
+                if ui
+                    .add_enabled(self.project.is_some(), egui::Button::new("Validate"))
+                    .clicked()
+                    && let Some(project) = &self.project
+                {
+                    self.problems = Some(crate::lint::check(project, self.encoding));
+                }
+
+                if ui
+                    .add_enabled(self.project.is_some(), egui::Button::new("Map Graph"))
+                    .clicked()
+                    && let Some(project) = &self.project
+                {
+                    self.graph = Some((crate::graph::Graph::build(project), Default::default()));
+                }
+
+                if ui
+                    .add_enabled(self.project.is_some(), egui::Button::new("Export Maps"))
+                    .clicked()
+                    && let Some(project) = &self.project
+                    && let Some(folder) = rfd::FileDialog::new().pick_folder()
+                {
+                    let options = self
+                        .map_view
+                        .as_ref()
+                        .map(crate::views::map::MapView::export_options)
+                        .unwrap_or_default();
+                    self.export_summary = Some(crate::export::export_all(
+                        project,
+                        &folder,
+                        &mut self.assets,
+                        options,
+                        self.encoding,
+                    ));
+                }
+
+                let mut map = self.map_view.is_some();
+                if ui.toggle_value(&mut map, "Map").changed() {
+                    self.map_view = map.then(Default::default);
+                }
+
+                let mut grid = self.grid_view.is_some();
+                if ui.toggle_value(&mut grid, "Tiles").changed() {
+                    self.grid_view = grid.then(Default::default);
+                }
+
+                if ui
+                    .add_enabled(self.project.is_some(), egui::Button::new("ChipSets"))
+                    .clicked()
+                    && let Some(project) = &self.project
+                {
+                    let selected = self.selected.and_then(|selected| {
+                        match &self.instances[selected].converted {
+                            Ok(lcf::Lcf::MapUnit(map_unit)) => Some(map_unit.chipset),
+                            _ => None,
                         }
-                        _ => (),
-                    };
-                    self.instances.push(Instance {
-                        name: path.file_name().unwrap().to_str().unwrap().to_owned(),
-                        converted: lcf.clone().try_into(),
-                        raw: lcf,
                     });
-                    self.selected = Some(self.instances.len() - 1);
+                    if let Some(id) = selected.or_else(|| project.chipset_ids().first().copied()) {
+                        self.chipset_view = Some(crate::views::chipset::ChipSetView::new(id));
+                    }
                 }
 
+                let mut save_diff = self.save_diff.is_some();
+                if ui.toggle_value(&mut save_diff, "Save Diff").changed() {
+                    self.save_diff = save_diff.then(Default::default);
+                }
+
+                let save_selected = self.selected.filter(|selected| {
+                    matches!(
+                        self.instances[*selected].raw,
+                        lcf::raw::RawLcf::RawSaveData(_)
+                    )
+                });
+                if ui
+                    .add_enabled(
+                        self.project.is_some() && save_selected.is_some(),
+                        egui::Button::new("Show Location"),
+                    )
+                    .on_disabled_hover_text("Open a project and one of its saves")
+                    .clicked()
+                    && let Some(index) = save_selected
+                {
+                    self.show_save_location(index);
+                }
+
+                let selected_save = self.selected.and_then(|selected| {
+                    let instance = &self.instances[selected];
+                    match &instance.raw {
+                        lcf::raw::RawLcf::RawSaveData(save) => Some((&instance.path, save)),
+                        _ => None,
+                    }
+                });
+                let mut save_editor = self.save_editor.is_some();
+                if ui
+                    .add_enabled(
+                        selected_save.is_some() || save_editor,
+                        egui::Button::selectable(save_editor, "Save Editor"),
+                    )
+                    .on_disabled_hover_text("Open a save file to edit it")
+                    .clicked()
+                {
+                    save_editor = !save_editor;
+                    self.save_editor = selected_save
+                        .filter(|_| save_editor)
+                        .map(|(path, save)| crate::save_editor::Editor::load(path.clone(), save));
+                }
+
+                ui.toggle_value(&mut self.interpreter, "Interpreter");
+
                 ui.menu_button("Encoding", |ui| {
                     for encoding in crate::code_page::ALL {
                         if ui.button(encoding.to_str()).clicked() {
@@ -74,6 +413,11 @@ impl eframe::App for App {
                     }
                 });
 
+                let mut hex = self.hex_view.is_some();
+                if ui.toggle_value(&mut hex, "Hex").changed() {
+                    self.hex_view = hex.then(Default::default);
+                }
+
                 ui.toggle_value(&mut self.using_raw, "Raw");
             });
         });
@@ -96,12 +440,355 @@ impl eframe::App for App {
             });
         }
 
+        if let Some(error) = &self.error {
+            let mut open = true;
+            egui::Window::new("Error")
+                .open(&mut open)
+                .collapsible(false)
+                .show(ctx, |ui| ui.label(error));
+            if !open {
+                self.error = None;
+            }
+        }
+
+        if let Some(summary) = &self.export_summary {
+            let mut open = true;
+            egui::Window::new("Export Maps")
+                .open(&mut open)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "Exported {} of {} maps",
+                        summary.written, summary.total
+                    ));
+                    if !summary.failures.is_empty() {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            for (id, err) in &summary.failures {
+                                ui.colored_label(
+                                    ui.visuals().error_fg_color,
+                                    format!("Map{id:04}: {err}"),
+                                );
+                            }
+                        });
+                    }
+                });
+            if !open {
+                self.export_summary = None;
+            }
+        }
+
+        if let (Some(project), Some(report)) = (&self.project, &self.report) {
+            let mut close = false;
+            egui::SidePanel::left("report").show(ctx, |ui| {
+                close = ui.button("Close").clicked();
+                if let Some(target) = crate::report::show(ui, project, report, self.encoding) {
+                    self.references = Some(target);
+                }
+            });
+            if close {
+                self.report = None;
+            }
+        }
+
+        if let (Some(project), Some(problems)) = (&self.project, &self.problems) {
+            let mut close = false;
+            let mut clicked = None;
+            egui::TopBottomPanel::bottom("problems")
+                .resizable(true)
+                .show(ctx, |ui| {
+                    close = ui.button("Close").clicked();
+                    clicked = crate::lint::show(ui, project, problems, self.encoding);
+                });
+            if close {
+                self.problems = None;
+            }
+            if let Some(location) = clicked {
+                self.goto(location);
+            }
+        }
+
+        if let (Some(project), Some((graph, layout))) = (&self.project, &mut self.graph) {
+            let mut open = true;
+            let mut clicked = None;
+            egui::Window::new("Map Graph")
+                .open(&mut open)
+                .default_size([640.0, 480.0])
+                .show(ctx, |ui| {
+                    if ui.button("Export DOT").clicked()
+                        && let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Graphviz", &["dot", "gv"])
+                            .set_file_name("maps.dot")
+                            .save_file()
+                        && let Err(err) = std::fs::write(path, graph.to_dot(project, self.encoding))
+                    {
+                        self.error = Some(format!("Failed to export graph: {err}"));
+                    }
+                    clicked = crate::graph::show(ui, project, graph, layout, self.encoding);
+                });
+            if !open {
+                self.graph = None;
+            }
+            if let Some(location) = clicked {
+                self.goto(location);
+            }
+        }
+
+        if let (Some(view), Some(selected)) = (&mut self.grid_view, self.selected)
+            && let Ok(lcf::Lcf::MapUnit(map_unit)) = &self.instances[selected].converted
+        {
+            let mut open = true;
+            egui::Window::new("Tiles")
+                .open(&mut open)
+                .default_size([640.0, 480.0])
+                .show(ctx, |ui| crate::views::grid::show(ui, view, map_unit));
+            if !open {
+                self.grid_view = None;
+            }
+        }
+
+        if let (Some(project), Some(view)) = (&self.project, &mut self.chipset_view) {
+            let mut open = true;
+            let mut clicked = None;
+            egui::Window::new("ChipSet")
+                .open(&mut open)
+                .default_size([640.0, 480.0])
+                .show(ctx, |ui| {
+                    clicked = crate::views::chipset::show(
+                        ui,
+                        view,
+                        project,
+                        &mut self.assets,
+                        self.encoding,
+                    );
+                });
+            if !open {
+                self.chipset_view = None;
+            }
+            if let Some(location) = clicked {
+                self.goto(location);
+            }
+        }
+
+        if let Some(view) = &mut self.save_diff {
+            let mut open = true;
+            egui::Window::new("Save Diff")
+                .open(&mut open)
+                .default_size([640.0, 480.0])
+                .show(ctx, |ui| {
+                    crate::save_diff::show(ui, view, self.project.as_ref(), self.encoding)
+                });
+            if !open {
+                self.save_diff = None;
+            }
+        }
+
+        if let Some(editor) = &mut self.save_editor {
+            let mut open = true;
+            // The editor follows the tab of its save, and closes with it.
+            let instance = self
+                .instances
+                .iter_mut()
+                .find(|instance| instance.path == editor.path);
+            if let Some(instance) = instance
+                && let lcf::raw::RawLcf::RawSaveData(save) = &mut instance.raw
+            {
+                let mut changed = false;
+                egui::Window::new("Save Editor")
+                    .open(&mut open)
+                    .default_size([640.0, 480.0])
+                    .show(ctx, |ui| {
+                        changed = crate::save_editor::show(
+                            ui,
+                            editor,
+                            save,
+                            self.project.as_ref(),
+                            self.encoding,
+                        );
+                    });
+                if changed {
+                    instance.converted = instance.raw.clone().try_into();
+                    if let Err(err) = instance.reload_bytes() {
+                        self.error = Some(err);
+                    }
+                }
+            } else {
+                open = false;
+            }
+            if !open {
+                self.save_editor = None;
+            }
+        }
+
+        if self.interpreter {
+            let mut open = true;
+            let mut clicked = None;
+            egui::Window::new("Interpreter")
+                .open(&mut open)
+                .default_size([640.0, 480.0])
+                .show(ctx, |ui| {
+                    let save =
+                        self.selected
+                            .and_then(|selected| match &self.instances[selected].raw {
+                                lcf::raw::RawLcf::RawSaveData(save) => Some(save),
+                                _ => None,
+                            });
+                    match save {
+                        Some(save) => {
+                            clicked = crate::interpreter::show(
+                                ui,
+                                save,
+                                self.project.as_ref(),
+                                self.encoding,
+                            );
+                        }
+                        None => {
+                            ui.label("Select a save file to see the events it was running.");
+                        }
+                    }
+                });
+            self.interpreter = open;
+            if let Some(location) = clicked {
+                self.goto(location);
+            }
+        }
+
+        if let Some(target) = self.references {
+            let mut clicked = None;
+            egui::SidePanel::right("references").show(ctx, |ui| {
+                if ui.button("Close").clicked() {
+                    self.references = None;
+                }
+                clicked = crate::references::show(ui, self.project.as_ref(), target, self.encoding);
+            });
+            if let Some(location) = clicked {
+                self.goto(location);
+            }
+        }
+
+        let root = match &self.project {
+            Some(project) => Some(project.root.clone()),
+            None => self.selected.and_then(|selected| {
+                self.instances[selected]
+                    .path
+                    .parent()
+                    .map(ToOwned::to_owned)
+            }),
+        };
+        self.assets.set_root(root);
+
+        if let (Some(view), Some(selected)) = (&mut self.map_view, self.selected)
+            && let Instance {
+                path,
+                converted: Ok(lcf::Lcf::MapUnit(map_unit)),
+                ..
+            } = &self.instances[selected]
+        {
+            let mut open = true;
+            let mut clicked = None;
+            let mut import = None;
+            egui::Window::new("Map")
+                .open(&mut open)
+                .default_size([640.0, 480.0])
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        let project = self.project.as_ref();
+                        if ui
+                            .add_enabled(project.is_some(), egui::Button::new("Export Tiled"))
+                            .clicked()
+                            && let Some(project) = project
+                            && let Some(folder) = rfd::FileDialog::new().pick_folder()
+                        {
+                            let name = path
+                                .file_stem()
+                                .map(|stem| stem.to_string_lossy().into_owned())
+                                .unwrap_or_default();
+                            if let Err(err) = crate::tiled::export(
+                                project,
+                                map_unit,
+                                &name,
+                                &folder,
+                                &mut self.assets,
+                                self.encoding,
+                            ) {
+                                self.error = Some(format!("Failed to export to Tiled: {err}"));
+                            }
+                        }
+                        if ui.button("Import Tiled").clicked() {
+                            import = rfd::FileDialog::new()
+                                .add_filter("Tiled map", &["tmx"])
+                                .pick_file();
+                        }
+                    });
+                    clicked = crate::views::map::show(
+                        ui,
+                        view,
+                        path,
+                        map_unit,
+                        self.project.as_ref(),
+                        &mut self.assets,
+                        self.encoding,
+                    );
+                });
+            if let Some(err) = view.error.take() {
+                self.error = Some(err);
+            }
+            if !open {
+                self.map_view = None;
+            }
+            if let Some(event) = clicked {
+                self.using_raw = false;
+                self.reveal = crate::views::map_unit::event_node_path(map_unit, event);
+            }
+            if let Some(tmx) = import {
+                self.import_tiled(selected, &tmx);
+            }
+        }
+
+        if let (Some(view), Some(selected)) = (&mut self.hex_view, self.selected) {
+            let instance = &self.instances[selected];
+            let mut clicked = None;
+            egui::SidePanel::right("hex")
+                .default_width(560.0)
+                .show(ctx, |ui| {
+                    // Node ids are those of the raw tree, so the converted one has none selected.
+                    let selected =
+                        egui_ltreeview::TreeViewState::<u64>::load(ui, egui::Id::new("tree"))
+                            .and_then(|state| state.selected().first().copied())
+                            .filter(|_| self.using_raw);
+                    clicked = crate::views::hex::show(
+                        ui,
+                        view,
+                        &instance.bytes,
+                        &instance.spans,
+                        selected,
+                        self.encoding,
+                    );
+                });
+            if let Some(path) = clicked {
+                self.using_raw = true;
+                self.reveal = Some(path);
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             if let Some(selected) = self.selected {
                 let Instance { raw, converted, .. } = &self.instances[selected];
 
+                let id = egui::Id::new("tree");
+                if let Some(path) = self.reveal.take() {
+                    let mut state =
+                        egui_ltreeview::TreeViewState::<u64>::load(ui, id).unwrap_or_default();
+                    for node in &path {
+                        state.set_openness(*node, true);
+                    }
+                    if let Some(node) = path.last() {
+                        state.set_one_selected(*node);
+                    }
+                    state.store(ui, id);
+                }
+
                 egui::ScrollArea::both().show(ui, |ui| {
-                    egui_ltreeview::TreeView::new("tree".into()).show(ui, |builder| {
+                    egui_ltreeview::TreeView::new(id).show(ui, |builder| {
                         if self.using_raw {
                             match raw {
                                 lcf::raw::RawLcf::RawDataBase(database) => {
@@ -109,6 +796,8 @@ impl eframe::App for App {
                                         database,
                                         builder,
                                         self.encoding,
+                                        &mut self.references,
+                                        &mut self.assets,
                                     )
                                 }
                                 lcf::raw::RawLcf::RawMapTree(map_tree) => {
@@ -130,6 +819,8 @@ impl eframe::App for App {
                                         save_data,
                                         builder,
                                         self.encoding,
+                                        self.project.as_ref(),
+                                        &mut self.save_filters,
                                     )
                                 }
                             };
@@ -144,7 +835,18 @@ impl eframe::App for App {
                                 }
                                 Ok(lcf::Lcf::MapTree(_map_tree)) => todo!(),
                                 Ok(lcf::Lcf::MapUnit(map_unit)) => {
-                                    crate::views::map_unit::update(map_unit, builder, self.encoding)
+                                    let raw = match raw {
+                                        lcf::raw::RawLcf::RawMapUnit(raw) => Some(raw),
+                                        _ => None,
+                                    };
+                                    crate::views::map_unit::update(
+                                        map_unit,
+                                        raw,
+                                        builder,
+                                        self.encoding,
+                                        self.project.as_ref(),
+                                        &mut self.assets,
+                                    )
                                 }
                                 Ok(lcf::Lcf::SaveData(_save_data)) => todo!(),
                                 Err(err) => builder.leaf(0, format!("An error occurred: {err:?}")),
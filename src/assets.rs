@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    io::Cursor,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use image::RgbaImage;
+
+use crate::render::Frame;
+
+/// Looks up an image or sound by name in one of a game's asset folders.
+///
+/// Names are matched case-insensitively and without extension, like the engine does.
+pub fn find(root: &Path, folder: &str, name: &str) -> Option<PathBuf> {
+    std::fs::read_dir(root.join(folder))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem.eq_ignore_ascii_case(name))
+        })
+}
+
+/// Loads a PNG, BMP or XYZ image, making palette index 0 transparent like the engine does.
+pub fn load(path: &Path) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path)?;
+    if bytes.starts_with(b"XYZ1") {
+        return load_xyz(&bytes);
+    }
+
+    let mut image = image::load_from_memory(&bytes)?.to_rgba8();
+    let key = if bytes.starts_with(b"\x89PNG") {
+        let reader = png::Decoder::new(Cursor::new(&bytes)).read_info()?;
+        reader
+            .info()
+            .palette
+            .as_ref()
+            .and_then(|palette| palette.get(..3))
+            .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+    } else {
+        image::codecs::bmp::BmpDecoder::new(Cursor::new(&bytes))?
+            .get_palette()
+            .and_then(|palette| palette.first().copied())
+    };
+
+    if let Some(key) = key {
+        for pixel in image.pixels_mut() {
+            if pixel.0[..3] == key {
+                pixel.0[3] = 0;
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// RPG Maker's own format: a zlib-compressed 256 colour palette followed by one index per pixel.
+fn load_xyz(bytes: &[u8]) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+    let header = bytes.get(4..8).ok_or("XYZ header is truncated")?;
+    let width = u16::from_le_bytes([header[0], header[1]]) as u32;
+    let height = u16::from_le_bytes([header[2], header[3]]) as u32;
+    let data = miniz_oxide::inflate::decompress_to_vec_zlib(&bytes[8..])
+        .map_err(|err| format!("XYZ data is corrupt: {err:?}"))?;
+    let (palette, indices) = data
+        .split_at_checked(768)
+        .ok_or("XYZ palette is truncated")?;
+
+    let mut image = RgbaImage::new(width, height);
+    for (pixel, &index) in image.pixels_mut().zip(indices) {
+        let rgb = &palette[index as usize * 3..][..3];
+        let alpha = if index == 0 { 0 } else { 255 };
+        *pixel = image::Rgba([rgb[0], rgb[1], rgb[2], alpha]);
+    }
+    Ok(image)
+}
+
+/// Images of the game being explored, loaded on first use.
+#[derive(Default)]
+pub struct Assets {
+    root: Option<PathBuf>,
+    images: HashMap<(&'static str, Vec<u8>), Option<Arc<RgbaImage>>>,
+    textures: HashMap<(&'static str, Vec<u8>), egui::TextureHandle>,
+    palettes: HashMap<(Vec<u8>, bool, Frame), egui::TextureHandle>,
+}
+
+impl Assets {
+    /// Changes the game folder, forgetting everything loaded from the previous one.
+    pub fn set_root(&mut self, root: Option<PathBuf>) {
+        if self.root != root {
+            self.root = root;
+            self.images.clear();
+            self.textures.clear();
+            self.palettes.clear();
+        }
+    }
+
+    pub fn image(
+        &mut self,
+        folder: &'static str,
+        name: &[u8],
+        encoding: crate::code_page::CodePage,
+    ) -> Option<Arc<RgbaImage>> {
+        let root = self.root.as_deref();
+        self.images
+            .entry((folder, name.to_vec()))
+            .or_insert_with(|| {
+                let name = encoding.to_encoding().decode(name).0;
+                let path = find(root?, folder, &name)?;
+                load(&path)
+                    .inspect_err(|err| eprintln!("Failed to load {}: {err}", path.display()))
+                    .ok()
+                    .map(Arc::new)
+            })
+            .clone()
+    }
+
+    pub fn texture(
+        &mut self,
+        ctx: &egui::Context,
+        folder: &'static str,
+        name: &[u8],
+        encoding: crate::code_page::CodePage,
+    ) -> Option<egui::TextureHandle> {
+        if let Some(texture) = self.textures.get(&(folder, name.to_vec())) {
+            return Some(texture.clone());
+        }
+
+        let image = self.image(folder, name, encoding)?;
+        let texture = ctx.load_texture(
+            format!("{folder}/{}", encoding.to_encoding().decode(name).0),
+            egui::ColorImage::from_rgba_unmultiplied(
+                [image.width() as usize, image.height() as usize],
+                image.as_raw(),
+            ),
+            egui::TextureOptions::NEAREST,
+        );
+        self.textures
+            .insert((folder, name.to_vec()), texture.clone());
+        Some(texture)
+    }
+
+    /// The lower or upper layer tiles of a chipset, laid out like the editor's palette.
+    pub fn palette(
+        &mut self,
+        ctx: &egui::Context,
+        file: &[u8],
+        upper: bool,
+        frame: Frame,
+        encoding: crate::code_page::CodePage,
+    ) -> Option<egui::TextureHandle> {
+        if let Some(texture) = self.palettes.get(&(file.to_vec(), upper, frame)) {
+            return Some(texture.clone());
+        }
+
+        let chipset = self.image("ChipSet", file, encoding)?;
+        let image = crate::render::palette(&chipset, upper, frame);
+        let texture = ctx.load_texture(
+            format!("Palette/{}/{upper}", encoding.to_encoding().decode(file).0),
+            egui::ColorImage::from_rgba_unmultiplied(
+                [image.width() as usize, image.height() as usize],
+                image.as_raw(),
+            ),
+            egui::TextureOptions::NEAREST,
+        );
+        self.palettes
+            .insert((file.to_vec(), upper, frame), texture.clone());
+        Some(texture)
+    }
+}
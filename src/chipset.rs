@@ -0,0 +1,310 @@
+use lcf::{
+    helpers::{Array, Chunk},
+    raw::ldb::chipset::ChipSetChunk,
+};
+
+use crate::render::Frame;
+
+/// Number of entries in the lower layer tables: 3 water, 3 animated, 12 autotiles and 144 tiles.
+pub const LOWER_TILES: usize = 162;
+/// Number of entries in the upper layer table.
+pub const UPPER_TILES: usize = 144;
+/// Tiles per row of the editor's palette.
+pub const COLUMNS: usize = 6;
+
+/// Passability bit flags of a chipset tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Passability(pub u8);
+
+impl Passability {
+    pub const DOWN: u8 = 0x01;
+    pub const LEFT: u8 = 0x02;
+    pub const RIGHT: u8 = 0x04;
+    pub const UP: u8 = 0x08;
+    /// Drawn above characters, and takes its passability from the lower layer.
+    pub const STAR: u8 = 0x10;
+    /// Passable from the sides, like a wall top.
+    pub const SQUARE: u8 = 0x20;
+    /// Lets the action button reach events on the other side.
+    pub const COUNTER: u8 = 0x40;
+
+    pub fn contains(self, flag: u8) -> bool {
+        self.0 & flag == flag
+    }
+
+    /// The passable directions, `○` when all of them are or `×` when none are.
+    pub fn directions(self) -> String {
+        match self.0 & 0x0F {
+            0x0F => "○".to_string(),
+            0 => "×".to_string(),
+            _ => [
+                (Self::DOWN, '↓'),
+                (Self::LEFT, '←'),
+                (Self::RIGHT, '→'),
+                (Self::UP, '↑'),
+            ]
+            .into_iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, arrow)| arrow)
+            .collect(),
+        }
+    }
+
+    pub fn label(self) -> String {
+        let mut label = self.directions();
+        for (flag, name) in [
+            (Self::STAR, " ★"),
+            (Self::SQUARE, " □"),
+            (Self::COUNTER, " Counter"),
+        ] {
+            if self.contains(flag) {
+                label.push_str(name);
+            }
+        }
+        label
+    }
+}
+
+/// The per-tile tables of a chipset, with the editor's defaults for missing chunks.
+#[derive(Clone, Debug)]
+pub struct ChipSet {
+    pub terrain: Vec<u16>,
+    pub lower: Vec<Passability>,
+    pub upper: Vec<Passability>,
+    /// Water cycles through its frames 1-2-3-2 when this is 0, or 1-2-3 when it is 1.
+    pub animation_type: u32,
+    /// Tiles animate twice as fast when this is 1.
+    pub animation_speed: u32,
+}
+
+impl Default for ChipSet {
+    fn default() -> Self {
+        let mut upper = vec![Passability(0x0F); UPPER_TILES];
+        upper[0] = Passability(0x1F);
+        Self {
+            terrain: vec![1; LOWER_TILES],
+            lower: vec![Passability(0x0F); LOWER_TILES],
+            upper,
+            animation_type: 0,
+            animation_speed: 0,
+        }
+    }
+}
+
+impl ChipSet {
+    pub fn from_chunks(chunks: &Array<Chunk<ChipSetChunk>>) -> Self {
+        let mut chipset = Self::default();
+        for chunk in &chunks.inner_vec {
+            match &chunk.data {
+                ChipSetChunk::Terrain(items) => chipset.terrain = items.to_vec(),
+                ChipSetChunk::PassabilityLower(items) => {
+                    chipset.lower = items.iter().map(|val| Passability(val.0 as u8)).collect();
+                }
+                ChipSetChunk::PassabilityUpper(items) => {
+                    chipset.upper = items.iter().map(|val| Passability(val.0 as u8)).collect();
+                }
+                ChipSetChunk::AnimationType(val) => chipset.animation_type = val.0,
+                ChipSetChunk::AnimationSpeed(val) => chipset.animation_speed = val.0,
+                _ => (),
+            }
+        }
+        chipset
+    }
+
+    /// Seconds each animation frame is shown for: 24 or 12 frames at 60 frames per second.
+    pub fn frame_duration(&self) -> f64 {
+        if self.animation_speed == 1 { 0.2 } else { 0.4 }
+    }
+
+    /// The animation frame shown `time` seconds after the map was entered.
+    pub fn frame(&self, time: f64) -> Frame {
+        let step = (time / self.frame_duration()) as u32;
+        let water = if self.animation_type == 1 {
+            step % 3
+        } else {
+            [0, 1, 2, 1][step as usize % 4]
+        };
+        Frame {
+            water,
+            animated: step % 4,
+        }
+    }
+
+    /// Passability of a lower or upper layer tile.
+    pub fn passability(&self, tile: u16) -> Option<Passability> {
+        match upper_index(tile) {
+            Some(index) => self.upper.get(index).copied(),
+            None => self.lower.get(lower_index(tile)?).copied(),
+        }
+    }
+
+    /// Terrain id of a lower layer tile.
+    pub fn terrain(&self, tile: u16) -> Option<u16> {
+        self.terrain.get(lower_index(tile)?).copied()
+    }
+
+    /// Passability of a map cell. Upper layer tiles decide unless they are star tiles.
+    pub fn cell_passability(&self, lower: u16, upper: u16) -> Option<Passability> {
+        match self.passability(upper) {
+            Some(passability) if !passability.contains(Passability::STAR) => Some(passability),
+            _ => self.passability(lower),
+        }
+    }
+}
+
+/// Index of a lower layer tile in the passability and terrain tables.
+pub fn lower_index(tile: u16) -> Option<usize> {
+    let index = match tile {
+        0..3000 => tile / 1000,
+        3000..3150 => 3 + (tile - 3000) / 50,
+        4000..4600 => 6 + (tile - 4000) / 50,
+        5000..5144 => 18 + tile - 5000,
+        _ => return None,
+    };
+    Some(index as usize)
+}
+
+/// Index of an upper layer tile in the passability table.
+pub fn upper_index(tile: u16) -> Option<usize> {
+    (10000..10144)
+        .contains(&tile)
+        .then(|| (tile - 10000) as usize)
+}
+
+/// The tile id shown for each entry of the lower layer tables, in the editor's palette order.
+pub fn lower_tile(index: usize) -> u16 {
+    let index = index as u16;
+    match index {
+        0..3 => index * 1000,
+        3..6 => 3000 + (index - 3) * 50,
+        6..18 => 4000 + (index - 6) * 50,
+        _ => 5000 + index - 18,
+    }
+}
+
+/// The section of the chipset sheet a palette entry comes from, as the editor names them.
+pub fn section(upper: bool, index: usize) -> &'static str {
+    match (upper, index) {
+        (true, _) => "F",
+        (false, 0..2) => "A",
+        (false, 2) => "B",
+        (false, 3..6) => "C",
+        (false, 6..18) => "D",
+        (false, _) => "E",
+    }
+}
+
+/// Every tile id that shares a palette entry, like the 50 neighbour variants of an autotile.
+pub fn tile_ids(upper: bool, index: usize) -> std::ops::RangeInclusive<u16> {
+    if upper {
+        let tile = 10000 + index as u16;
+        return tile..=tile;
+    }
+    let first = lower_tile(index);
+    match index {
+        0..3 => first..=first + 999,
+        3..18 => first..=first + 49,
+        _ => first..=first,
+    }
+}
+
+/// Which of the 47 neighbour variants a water shore or autotile takes, given whether each of its
+/// neighbours joins it, starting north and going clockwise. These are the variants the `WATER`
+/// and `AUTOTILE` tables of the renderer draw.
+pub fn neighbour_variant(joins: [bool; 8]) -> u16 {
+    let [n, ne, e, se, s, sw, w, nw] = joins.map(|joins| u16::from(!joins));
+    // Inner corners only show where both sides next to them join.
+    match (n, e, s, w) {
+        (0, 0, 0, 0) => nw | ne << 1 | se << 2 | sw << 3,
+        (0, 0, 0, 1) => 16 + ne + (se << 1),
+        (1, 0, 0, 0) => 20 + se + (sw << 1),
+        (0, 1, 0, 0) => 24 + sw + (nw << 1),
+        (0, 0, 1, 0) => 28 + nw + (ne << 1),
+        (0, 1, 0, 1) => 32,
+        (1, 0, 1, 0) => 33,
+        (1, 0, 0, 1) => 34 + se,
+        (1, 1, 0, 0) => 36 + sw,
+        (0, 1, 1, 0) => 38 + nw,
+        (0, 0, 1, 1) => 40 + ne,
+        (1, 1, 0, 1) => 42,
+        (1, 0, 1, 1) => 43,
+        (0, 1, 1, 1) => 44,
+        (1, 1, 1, 0) => 45,
+        _ => 46,
+    }
+}
+
+/// What a tile id stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    /// Shallow water of block A, with its shore variant and the deep water corners of block B.
+    WaterA {
+        shore: u16,
+        deep: u16,
+    },
+    WaterB {
+        shore: u16,
+        deep: u16,
+    },
+    DeepWater {
+        shore: u16,
+        deep: u16,
+    },
+    Animated(u16),
+    /// One of the 12 autotiles and which of its 50 neighbour variants is drawn.
+    Autotile {
+        index: u16,
+        variant: u16,
+    },
+    Lower(u16),
+    Upper(u16),
+    Unknown(u16),
+}
+
+impl Tile {
+    pub fn decode(tile: u16) -> Self {
+        let (shore, deep) = (tile % 50, tile % 1000 / 50);
+        match tile {
+            0..1000 => Self::WaterA { shore, deep },
+            1000..2000 => Self::WaterB { shore, deep },
+            2000..3000 => Self::DeepWater { shore, deep },
+            3000..3150 => Self::Animated((tile - 3000) / 50),
+            4000..4600 => Self::Autotile {
+                index: (tile - 4000) / 50,
+                variant: (tile - 4000) % 50,
+            },
+            5000..5144 => Self::Lower(tile - 5000),
+            10000..10144 => Self::Upper(tile - 10000),
+            _ => Self::Unknown(tile),
+        }
+    }
+
+    /// A few characters naming the tile, to fit in a grid cell.
+    pub fn short(self) -> String {
+        match self {
+            Self::WaterA { shore, .. } => format!("A1.{shore}"),
+            Self::WaterB { shore, .. } => format!("A2.{shore}"),
+            Self::DeepWater { shore, .. } => format!("B.{shore}"),
+            Self::Animated(index) => format!("C{}", index + 1),
+            Self::Autotile { index, variant } => format!("D{}.{variant}", index + 1),
+            Self::Lower(index) => format!("E{index}"),
+            Self::Upper(index) => format!("F{index}"),
+            Self::Unknown(tile) => format!("?{tile}"),
+        }
+    }
+
+    pub fn describe(self) -> String {
+        match self {
+            Self::WaterA { shore, deep } => format!("Water A, shore {shore}, deep {deep}"),
+            Self::WaterB { shore, deep } => format!("Water B, shore {shore}, deep {deep}"),
+            Self::DeepWater { shore, deep } => format!("Deep water, shore {shore}, deep {deep}"),
+            Self::Animated(index) => format!("Animated tile {}", index + 1),
+            Self::Autotile { index, variant } => {
+                format!("Autotile {}, variant {variant}", index + 1)
+            }
+            Self::Lower(index) => format!("Lower tile {index}"),
+            Self::Upper(index) => format!("Upper tile {index}"),
+            Self::Unknown(tile) => format!("Unknown tile {tile}"),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! What the command line subcommands share.
+
+use crate::code_page::CodePage;
+
+/// Runs the subcommand named by the first argument, returning its exit code, or `None` when
+/// there is none and the window should open.
+pub fn run(args: &[String]) -> Option<i32> {
+    let (command, args) = args.split_first()?;
+    match command.as_str() {
+        "export" => Some(crate::export::cli(args)),
+        "diff" => Some(crate::save_diff::cli(args)),
+        _ => None,
+    }
+}
+
+/// Reads the options every subcommand takes, `--encoding` and `--help`, returning the encoding
+/// and the other arguments, or the exit code when the subcommand should stop.
+pub fn common<'a>(args: &'a [String], usage: &str) -> Result<(CodePage, Vec<&'a str>), i32> {
+    let mut encoding = CodePage::default();
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--encoding" => match args
+                .next()
+                .and_then(|name| crate::code_page::from_name(name))
+            {
+                Some(code_page) => encoding = code_page,
+                None => return Err(self::usage(usage, "unknown encoding")),
+            },
+            "-h" | "--help" => return Err(self::usage(usage, "")),
+            arg => rest.push(arg),
+        }
+    }
+    Ok((encoding, rest))
+}
+
+/// Prints `usage`, after `error` on stderr when there is one, returning the exit code.
+pub fn usage(usage: &str, error: &str) -> i32 {
+    if error.is_empty() {
+        println!("{usage}");
+        return 0;
+    }
+    eprintln!("{error}\n\n{usage}");
+    2
+}
@@ -16,6 +16,13 @@ pub const ALL: &[CodePage] = &[
     CodePage::ShiftJIS,
 ];
 
+/// The code page whose encoding has this name, like `windows-1252` or `Shift_JIS`.
+pub fn from_name(name: &str) -> Option<CodePage> {
+    ALL.iter()
+        .find(|code_page| code_page.to_encoding().name().eq_ignore_ascii_case(name))
+        .copied()
+}
+
 impl CodePage {
     pub const fn to_str(self) -> &'static str {
         match self {
@@ -0,0 +1,198 @@
+//! Map screenshots as PNG files, from the map window or headless from the command line.
+
+use std::path::{Path, PathBuf};
+
+use image::RgbaImage;
+
+use crate::{
+    assets::Assets,
+    project::{Map, Project},
+    render::Layers,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub layers: Layers,
+    pub events: bool,
+    pub grid: bool,
+    pub passability: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            layers: Layers::default(),
+            events: true,
+            grid: false,
+            passability: false,
+        }
+    }
+}
+
+/// Draws a map at full resolution, with everything `options` asks for.
+pub fn render(
+    project: &Project,
+    map_unit: &lcf::lmu::LcfMapUnit,
+    assets: &mut Assets,
+    options: Options,
+    encoding: crate::code_page::CodePage,
+) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+    let file = project
+        .chipset_file(map_unit.chipset)
+        .ok_or_else(|| format!("chipset {} is not in the database", map_unit.chipset))?;
+    let chipset = assets.image("ChipSet", file, encoding).ok_or_else(|| {
+        format!(
+            "chipset image {} was not found",
+            encoding.to_encoding().decode(file).0
+        )
+    })?;
+
+    let mut image = crate::render::render(map_unit, &chipset, options.layers, Default::default());
+    if options.events {
+        crate::render::draw_events(&mut image, map_unit, &chipset, assets, encoding);
+    }
+    if options.passability {
+        let tables = project.chipset(map_unit.chipset).unwrap_or_default();
+        crate::render::draw_passability(&mut image, map_unit, &tables);
+    }
+    if options.grid {
+        crate::render::draw_grid(&mut image);
+    }
+    Ok(image)
+}
+
+pub fn export(
+    project: &Project,
+    map_unit: &lcf::lmu::LcfMapUnit,
+    path: &Path,
+    assets: &mut Assets,
+    options: Options,
+    encoding: crate::code_page::CodePage,
+) -> Result<(), Box<dyn std::error::Error>> {
+    render(project, map_unit, assets, options, encoding)?
+        .save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+/// File name of a map's screenshot, like `Map0001.png`.
+pub fn file_name(map: &Map) -> String {
+    format!("Map{:04}.png", map.id)
+}
+
+/// What exporting every map of a project did.
+pub struct Summary {
+    pub written: usize,
+    pub total: usize,
+    /// Maps that failed to export, with why.
+    pub failures: Vec<(u32, String)>,
+}
+
+/// Exports every map of a project into `folder`. Maps that fail are skipped.
+pub fn export_all(
+    project: &Project,
+    folder: &Path,
+    assets: &mut Assets,
+    options: Options,
+    encoding: crate::code_page::CodePage,
+) -> Summary {
+    let mut summary = Summary {
+        written: 0,
+        total: project.maps.len(),
+        failures: Vec::new(),
+    };
+    for map in &project.maps {
+        let path = folder.join(file_name(map));
+        match export(project, &map.unit, &path, assets, options, encoding) {
+            Ok(()) => summary.written += 1,
+            Err(err) => summary.failures.push((map.id, err.to_string())),
+        }
+    }
+    summary
+}
+
+const USAGE: &str = "\
+Usage: lcf-explorer export <project folder> <output folder> [options]
+
+Options:
+    --map <id>           Only export this map. Can be given more than once.
+    --no-lower           Leave out the lower layer.
+    --no-upper           Leave out the upper layer.
+    --no-events          Leave out events.
+    --grid               Draw lines between tiles.
+    --passability        Mark blocked sides of tiles.
+    --encoding <name>    Text encoding of file names, like windows-1252 or Shift_JIS.";
+
+/// Runs `lcf-explorer export` with the arguments after it, returning the exit code.
+pub fn cli(args: &[String]) -> i32 {
+    let mut options = Options::default();
+    let mut maps = Vec::new();
+    let mut folders = Vec::new();
+
+    let (encoding, args) = match crate::cli::common(args, USAGE) {
+        Ok(common) => common,
+        Err(code) => return code,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--map" => match args.next().and_then(|id| id.parse::<u32>().ok()) {
+                Some(id) => maps.push(id),
+                None => return crate::cli::usage(USAGE, "--map needs a map id"),
+            },
+            "--no-lower" => options.layers.lower = false,
+            "--no-upper" => options.layers.upper = false,
+            "--no-events" => options.events = false,
+            "--grid" => options.grid = true,
+            "--passability" => options.passability = true,
+            _ if arg.starts_with("--") => {
+                return crate::cli::usage(USAGE, &format!("unknown option {arg}"));
+            }
+            _ => folders.push(PathBuf::from(arg)),
+        }
+    }
+    let [root, output] = folders.as_slice() else {
+        return crate::cli::usage(USAGE, "expected a project folder and an output folder");
+    };
+
+    let project = match Project::open(root) {
+        Ok(project) => project,
+        Err(err) => {
+            eprintln!("Failed to open project: {err}");
+            return 1;
+        }
+    };
+    if let Err(err) = std::fs::create_dir_all(output) {
+        eprintln!("Failed to create {}: {err}", output.display());
+        return 1;
+    }
+
+    let mut assets = Assets::default();
+    assets.set_root(Some(project.root.clone()));
+
+    if maps.is_empty() {
+        let summary = export_all(&project, output, &mut assets, options, encoding);
+        for (id, err) in &summary.failures {
+            eprintln!("Failed to export map {id}: {err}");
+        }
+        println!("Exported {} of {} maps", summary.written, summary.total);
+        return if summary.failures.is_empty() { 0 } else { 1 };
+    }
+
+    let mut code = 0;
+    for id in maps {
+        let Some(map) = project.map(id) else {
+            eprintln!("Map {id} is not in the project");
+            code = 1;
+            continue;
+        };
+        let path = output.join(file_name(map));
+        match export(&project, &map.unit, &path, &mut assets, options, encoding) {
+            Ok(()) => println!("Exported {}", path.display()),
+            Err(err) => {
+                eprintln!("Failed to export map {id}: {err}");
+                code = 1;
+            }
+        }
+    }
+    code
+}
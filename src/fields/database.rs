@@ -0,0 +1,463 @@
+//! Chunks of the database records, as RPG Maker 2000 and 2003 write them.
+
+use super::{
+    Field,
+    Kind::{Bool, Bytes, Longs, Number, Records, Shorts, Struct, Text},
+    field,
+};
+
+pub const SOUND: &[Field] = &[
+    field(1, "Name", Text),
+    field(3, "Volume", Number),
+    field(4, "Tempo", Number),
+    field(5, "Balance", Number),
+];
+
+pub const ACTOR_SKILL: &[Field] = &[field(1, "Level", Number), field(2, "Skill", Number)];
+
+pub const ACTOR: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Title", Text),
+    field(3, "Charset", Text),
+    field(4, "Charset Index", Number),
+    field(5, "Transparent", Bool),
+    field(7, "Initial Level", Number),
+    field(8, "Final Level", Number),
+    field(9, "Critical Hits", Bool),
+    field(10, "Critical Hit Chance", Number),
+    field(15, "Faceset", Text),
+    field(16, "Faceset Index", Number),
+    field(21, "Two Weapons", Bool),
+    field(22, "Lock Equipment", Bool),
+    field(23, "Auto Battle", Bool),
+    field(24, "Strong Defense", Bool),
+    field(31, "Parameters", Shorts),
+    field(41, "Experience Base", Number),
+    field(42, "Experience Inflation", Number),
+    field(43, "Experience Correction", Number),
+    field(51, "Initial Equipment", Shorts),
+    field(56, "Unarmed Animation", Number),
+    field(57, "Class", Number),
+    field(59, "Battle X", Number),
+    field(60, "Battle Y", Number),
+    field(62, "Battler Animation", Number),
+    field(63, "Skills", Records(ACTOR_SKILL)),
+    field(66, "Rename Skill Category", Bool),
+    field(67, "Skill Category Name", Text),
+    field(71, "State Ranks Size", Number),
+    field(72, "State Ranks", Bytes),
+    field(73, "Attribute Ranks Size", Number),
+    field(74, "Attribute Ranks", Bytes),
+    field(80, "Battle Commands", Longs),
+];
+
+pub const SKILL_ANIMATION: &[Field] = &[
+    field(5, "Movement", Number),
+    field(6, "After Image", Number),
+    field(14, "Pose", Number),
+];
+
+pub const SKILL: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Description", Text),
+    field(3, "Using Message 1", Text),
+    field(4, "Using Message 2", Text),
+    field(7, "Failure Message", Number),
+    field(8, "Type", Number),
+    field(9, "SP Type", Number),
+    field(10, "SP Percent", Number),
+    field(11, "SP Cost", Number),
+    field(12, "Scope", Number),
+    field(13, "Switch", Number),
+    field(14, "Animation", Number),
+    field(16, "Sound Effect", Struct(SOUND)),
+    field(18, "Usable on Field", Bool),
+    field(19, "Usable in Battle", Bool),
+    field(20, "Inflict States", Bool),
+    field(21, "Physical Rate", Number),
+    field(22, "Magical Rate", Number),
+    field(23, "Variance", Number),
+    field(24, "Power", Number),
+    field(25, "Hit", Number),
+    field(31, "Affect HP", Bool),
+    field(32, "Affect SP", Bool),
+    field(33, "Affect Attack", Bool),
+    field(34, "Affect Defense", Bool),
+    field(35, "Affect Spirit", Bool),
+    field(36, "Affect Agility", Bool),
+    field(37, "Absorb Damage", Bool),
+    field(38, "Ignore Defense", Bool),
+    field(41, "State Effects Size", Number),
+    field(42, "State Effects", Bytes),
+    field(43, "Attribute Effects Size", Number),
+    field(44, "Attribute Effects", Bytes),
+    field(45, "Affect Attribute Defense", Bool),
+    field(49, "Battler Animation", Number),
+    field(50, "Battler Animation Data", Records(SKILL_ANIMATION)),
+];
+
+pub const ITEM_ANIMATION: &[Field] = &[
+    field(3, "Type", Number),
+    field(4, "Weapon Animation", Number),
+    field(5, "Movement", Number),
+    field(6, "After Image", Number),
+    field(7, "Attacks", Number),
+    field(8, "Ranged", Bool),
+    field(9, "Ranged Animation", Number),
+    field(12, "Ranged Speed", Number),
+    field(13, "Battle Animation", Number),
+];
+
+pub const ITEM: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Description", Text),
+    field(3, "Type", Number),
+    field(5, "Price", Number),
+    field(6, "Uses", Number),
+    field(11, "Attack", Number),
+    field(12, "Defense", Number),
+    field(13, "Spirit", Number),
+    field(14, "Agility", Number),
+    field(15, "Two Handed", Bool),
+    field(16, "SP Cost", Number),
+    field(17, "Hit", Number),
+    field(18, "Critical Hit", Number),
+    field(20, "Animation", Number),
+    field(21, "Preemptive", Bool),
+    field(22, "Dual Attack", Bool),
+    field(23, "Attack All", Bool),
+    field(24, "Ignore Evasion", Bool),
+    field(25, "Prevent Critical", Bool),
+    field(26, "Raise Evasion", Bool),
+    field(27, "Half SP Cost", Bool),
+    field(28, "No Terrain Damage", Bool),
+    field(29, "Cursed", Bool),
+    field(31, "Entire Party", Bool),
+    field(32, "Recover HP Rate", Number),
+    field(33, "Recover HP", Number),
+    field(34, "Recover SP Rate", Number),
+    field(35, "Recover SP", Number),
+    field(37, "Usable on Field", Bool),
+    field(38, "Only on Dead", Bool),
+    field(41, "Max HP", Number),
+    field(42, "Max SP", Number),
+    field(43, "Attack Bonus", Number),
+    field(44, "Defense Bonus", Number),
+    field(45, "Spirit Bonus", Number),
+    field(46, "Agility Bonus", Number),
+    field(51, "Using Message", Number),
+    field(53, "Skill", Number),
+    field(55, "Switch", Number),
+    field(57, "Switch Usable on Field", Bool),
+    field(58, "Usable in Battle", Bool),
+    field(61, "Actors Size", Number),
+    field(62, "Actors", Bytes),
+    field(63, "States Size", Number),
+    field(64, "States", Bytes),
+    field(65, "Attributes Size", Number),
+    field(66, "Attributes", Bytes),
+    field(67, "State Chance", Number),
+    field(68, "Inflict States", Bool),
+    field(69, "Weapon Animation", Number),
+    field(70, "Animation Data", Records(ITEM_ANIMATION)),
+    field(71, "Use Skill", Bool),
+    field(72, "Classes Size", Number),
+    field(73, "Classes", Bytes),
+    field(75, "Ranged Trajectory", Number),
+    field(76, "Ranged Target", Number),
+];
+
+pub const TERRAIN: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Damage", Number),
+    field(3, "Encounter Rate", Number),
+    field(4, "Battle Background", Text),
+    field(5, "Boat Passable", Bool),
+    field(6, "Ship Passable", Bool),
+    field(7, "Airship Passable", Bool),
+    field(9, "Airship Landable", Bool),
+    field(11, "Bush Depth", Number),
+    field(15, "Footstep", Struct(SOUND)),
+    field(16, "Sound on Damage", Bool),
+    field(17, "Background Type", Number),
+    field(21, "Background A", Text),
+    field(22, "Background A Scroll Horizontal", Bool),
+    field(23, "Background A Scroll Vertical", Bool),
+    field(24, "Background A Horizontal Speed", Number),
+    field(25, "Background A Vertical Speed", Number),
+    field(30, "Background B Exists", Bool),
+    field(31, "Background B", Text),
+    field(32, "Background B Scroll Horizontal", Bool),
+    field(33, "Background B Scroll Vertical", Bool),
+    field(34, "Background B Horizontal Speed", Number),
+    field(35, "Background B Vertical Speed", Number),
+    field(40, "Special Flags", Number),
+    field(41, "Back Attack (Party)", Number),
+    field(42, "Back Attack (Enemies)", Number),
+    field(43, "Pincer Attack (Party)", Number),
+    field(44, "Pincer Attack (Enemies)", Number),
+    field(45, "Grid Location", Number),
+    field(46, "Grid Top Y", Number),
+    field(47, "Grid Elongation", Number),
+    field(48, "Grid Inclination", Number),
+];
+
+pub const ANIMATION_TIMING: &[Field] = &[
+    field(1, "Frame", Number),
+    field(2, "Sound Effect", Struct(SOUND)),
+    field(3, "Flash Scope", Number),
+    field(4, "Flash Red", Number),
+    field(5, "Flash Green", Number),
+    field(6, "Flash Blue", Number),
+    field(7, "Flash Power", Number),
+    field(8, "Screen Shake", Number),
+];
+
+pub const ANIMATION_CELL: &[Field] = &[
+    field(1, "Visible", Bool),
+    field(2, "Cell", Number),
+    field(3, "X", Number),
+    field(4, "Y", Number),
+    field(5, "Zoom", Number),
+    field(6, "Red", Number),
+    field(7, "Green", Number),
+    field(8, "Blue", Number),
+    field(9, "Saturation", Number),
+    field(10, "Transparency", Number),
+];
+
+pub const ANIMATION_FRAME: &[Field] = &[field(1, "Cells", Records(ANIMATION_CELL))];
+
+pub const ANIMATION: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "File", Text),
+    field(3, "Large", Bool),
+    field(6, "Timings", Records(ANIMATION_TIMING)),
+    field(9, "Scope", Number),
+    field(10, "Position", Number),
+    field(12, "Frames", Records(ANIMATION_FRAME)),
+];
+
+pub const MUSIC: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Fade In", Number),
+    field(3, "Volume", Number),
+    field(4, "Tempo", Number),
+    field(5, "Balance", Number),
+];
+
+pub const ENEMY_ACTION: &[Field] = &[
+    field(1, "Kind", Number),
+    field(2, "Basic Action", Number),
+    field(3, "Skill", Number),
+    field(4, "Transform Into", Number),
+    field(5, "Condition Type", Number),
+    field(6, "Condition Min", Number),
+    field(7, "Condition Max", Number),
+    field(8, "Condition Switch", Number),
+    field(9, "Turn Switch On", Bool),
+    field(10, "Switch On", Number),
+    field(11, "Turn Switch Off", Bool),
+    field(12, "Switch Off", Number),
+    field(13, "Rating", Number),
+];
+
+pub const ENEMY: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Battler", Text),
+    field(3, "Hue", Number),
+    field(4, "Max HP", Number),
+    field(5, "Max SP", Number),
+    field(6, "Attack", Number),
+    field(7, "Defense", Number),
+    field(8, "Spirit", Number),
+    field(9, "Agility", Number),
+    field(10, "Transparent", Bool),
+    field(11, "Experience", Number),
+    field(12, "Gold", Number),
+    field(13, "Drop Item", Number),
+    field(14, "Drop Chance", Number),
+    field(21, "Critical Hits", Bool),
+    field(22, "Critical Hit Chance", Number),
+    field(26, "Miss", Bool),
+    field(28, "Levitate", Bool),
+    field(31, "State Ranks Size", Number),
+    field(32, "State Ranks", Bytes),
+    field(33, "Attribute Ranks Size", Number),
+    field(34, "Attribute Ranks", Bytes),
+    field(42, "Actions", Records(ENEMY_ACTION)),
+];
+
+pub const TROOP_MEMBER: &[Field] = &[
+    field(1, "Enemy", Number),
+    field(2, "X", Number),
+    field(3, "Y", Number),
+    field(4, "Appears Midway", Bool),
+];
+
+pub const TROOP_CONDITION: &[Field] = &[
+    field(1, "Flags", Bytes),
+    field(2, "Switch A", Number),
+    field(3, "Switch B", Number),
+    field(4, "Variable", Number),
+    field(5, "Variable Value", Number),
+    field(6, "Turn A", Number),
+    field(7, "Turn B", Number),
+    field(8, "Exhaustion Min", Number),
+    field(9, "Exhaustion Max", Number),
+    field(10, "Enemy", Number),
+    field(11, "Enemy HP Min", Number),
+    field(12, "Enemy HP Max", Number),
+    field(13, "Actor", Number),
+    field(14, "Actor HP Min", Number),
+    field(15, "Actor HP Max", Number),
+    field(16, "Turn Enemy", Number),
+    field(17, "Turn Enemy A", Number),
+    field(18, "Turn Enemy B", Number),
+    field(19, "Turn Actor", Number),
+    field(20, "Turn Actor A", Number),
+    field(21, "Turn Actor B", Number),
+    field(22, "Command Actor", Number),
+    field(23, "Command", Number),
+];
+
+pub const TROOP_PAGE: &[Field] = &[
+    field(2, "Condition", Struct(TROOP_CONDITION)),
+    field(11, "Commands Size", Number),
+    field(12, "Commands", Bytes),
+];
+
+pub const TROOP: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Members", Records(TROOP_MEMBER)),
+    field(3, "Auto Alignment", Bool),
+    field(4, "Terrain Size", Number),
+    field(5, "Terrain", Bytes),
+    field(6, "Appear Randomly", Bool),
+    field(11, "Pages", Records(TROOP_PAGE)),
+];
+
+pub const ATTRIBUTE: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Type", Number),
+    field(11, "Rate A", Number),
+    field(12, "Rate B", Number),
+    field(13, "Rate C", Number),
+    field(14, "Rate D", Number),
+    field(15, "Rate E", Number),
+];
+
+pub const STATE: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Type", Number),
+    field(3, "Color", Number),
+    field(4, "Priority", Number),
+    field(5, "Restriction", Number),
+    field(11, "Rate A", Number),
+    field(12, "Rate B", Number),
+    field(13, "Rate C", Number),
+    field(14, "Rate D", Number),
+    field(15, "Rate E", Number),
+    field(21, "Hold Turns", Number),
+    field(22, "Auto Release Chance", Number),
+    field(23, "Release by Damage", Number),
+    field(30, "Affect Type", Number),
+    field(31, "Affect Attack", Bool),
+    field(32, "Affect Defense", Bool),
+    field(33, "Affect Spirit", Bool),
+    field(34, "Affect Agility", Bool),
+    field(35, "Hit Rate", Number),
+    field(36, "Avoid Attacks", Bool),
+    field(37, "Reflect Magic", Bool),
+    field(38, "Cursed", Bool),
+    field(39, "Battler Animation", Number),
+    field(41, "Restrict Skills", Bool),
+    field(42, "Restrict Skill Level", Number),
+    field(43, "Restrict Magic", Bool),
+    field(44, "Restrict Magic Level", Number),
+    field(45, "HP Change Type", Number),
+    field(46, "SP Change Type", Number),
+    field(51, "Message (Actor)", Text),
+    field(52, "Message (Enemy)", Text),
+    field(53, "Message (Already)", Text),
+    field(54, "Message (Affected)", Text),
+    field(55, "Message (Recovery)", Text),
+    field(61, "HP Change Max", Number),
+    field(62, "HP Change", Number),
+    field(63, "HP Change Steps", Number),
+    field(64, "HP Change on Map", Number),
+    field(65, "SP Change Max", Number),
+    field(66, "SP Change", Number),
+    field(67, "SP Change Steps", Number),
+    field(68, "SP Change on Map", Number),
+];
+
+pub const BATTLE_TEST: &[Field] = &[
+    field(1, "Actor", Number),
+    field(2, "Level", Number),
+    field(11, "Weapon", Number),
+    field(12, "Shield", Number),
+    field(13, "Armor", Number),
+    field(14, "Helmet", Number),
+    field(15, "Accessory", Number),
+];
+
+pub const SYSTEM: &[Field] = &[
+    field(10, "Database Version", Number),
+    field(11, "Boat Charset", Text),
+    field(12, "Ship Charset", Text),
+    field(13, "Airship Charset", Text),
+    field(14, "Boat Charset Index", Number),
+    field(15, "Ship Charset Index", Number),
+    field(16, "Airship Charset Index", Number),
+    field(17, "Title Screen", Text),
+    field(18, "Game Over Screen", Text),
+    field(19, "System Graphic", Text),
+    field(20, "System Graphic 2", Text),
+    field(21, "Party Size", Number),
+    field(22, "Party", Shorts),
+    field(26, "Menu Commands Size", Number),
+    field(27, "Menu Commands", Shorts),
+    field(31, "Title Music", Struct(MUSIC)),
+    field(32, "Battle Music", Struct(MUSIC)),
+    field(33, "Victory Music", Struct(MUSIC)),
+    field(34, "Inn Music", Struct(MUSIC)),
+    field(35, "Boat Music", Struct(MUSIC)),
+    field(36, "Ship Music", Struct(MUSIC)),
+    field(37, "Airship Music", Struct(MUSIC)),
+    field(38, "Game Over Music", Struct(MUSIC)),
+    field(41, "Cursor Sound", Struct(SOUND)),
+    field(42, "Decision Sound", Struct(SOUND)),
+    field(43, "Cancel Sound", Struct(SOUND)),
+    field(44, "Buzzer Sound", Struct(SOUND)),
+    field(45, "Battle Start Sound", Struct(SOUND)),
+    field(46, "Escape Sound", Struct(SOUND)),
+    field(47, "Enemy Attack Sound", Struct(SOUND)),
+    field(48, "Enemy Damaged Sound", Struct(SOUND)),
+    field(49, "Actor Damaged Sound", Struct(SOUND)),
+    field(50, "Dodge Sound", Struct(SOUND)),
+    field(51, "Enemy Death Sound", Struct(SOUND)),
+    field(52, "Item Sound", Struct(SOUND)),
+    field(61, "Map Exit Transition", Number),
+    field(62, "Map Enter Transition", Number),
+    field(63, "Battle Start Erase", Number),
+    field(64, "Battle Start Show", Number),
+    field(65, "Battle End Erase", Number),
+    field(66, "Battle End Show", Number),
+    field(71, "Message Stretch", Number),
+    field(72, "Font", Number),
+    field(81, "Selected Condition", Number),
+    field(82, "Selected Actor", Number),
+    field(91, "Battle Test Background", Text),
+    field(92, "Battle Test Party", Records(BATTLE_TEST)),
+    field(93, "Save Count", Number),
+    field(94, "Battle Test Terrain", Number),
+    field(95, "Battle Test Formation", Number),
+    field(96, "Battle Test Condition", Number),
+    field(97, "Equipment Setting", Number),
+    field(98, "Battle Test Alternative Terrain", Number),
+    field(99, "Show Frame", Bool),
+    field(100, "Frame", Text),
+    field(101, "Invert Animations", Bool),
+    field(102, "Show Title", Bool),
+];
@@ -0,0 +1,173 @@
+//! Names and types of the chunks lcf leaves as plain bytes, so they can be shown as fields.
+
+use binrw::{BinRead, BinWrite};
+use lcf::helpers::{Array, Array2D, Chunk, Number, UnknownChunk};
+
+pub mod database;
+pub mod save_data;
+
+/// How the bytes of a chunk are laid out.
+#[derive(Clone, Copy, Debug)]
+pub enum Kind {
+    /// Text in the project's code page.
+    Text,
+    /// A single variable sized number.
+    Number,
+    /// A number that is either 0 or 1.
+    Bool,
+    /// One byte per entry, like the flags of which states an item cures.
+    Bytes,
+    /// Little endian `u16`s, like the parameter curves of an actor.
+    Shorts,
+    /// Little endian `u32`s.
+    Longs,
+    /// A little endian `f64`, like the position of a picture.
+    Double,
+    /// Nested chunks, like a sound effect.
+    Struct(&'static [Field]),
+    /// A list of records made of nested chunks, like the frames of an animation.
+    Records(&'static [Field]),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Field {
+    pub id: u32,
+    pub name: &'static str,
+    pub kind: Kind,
+}
+
+pub const fn field(id: u32, name: &'static str, kind: Kind) -> Field {
+    Field { id, name, kind }
+}
+
+pub fn find(fields: &[Field], id: u32) -> Option<&Field> {
+    fields.iter().find(|field| field.id == id)
+}
+
+/// The bytes of the chunk with `id`.
+pub fn get(chunks: &Array<Chunk<UnknownChunk>>, id: u32) -> Option<&[u8]> {
+    chunks.inner_vec.iter().find_map(|chunk| {
+        let UnknownChunk::Unknown {
+            id: chunk_id,
+            bytes,
+        } = &chunk.data;
+        (*chunk_id == id).then_some(bytes.as_slice())
+    })
+}
+
+/// Replaces the chunk with `id`, or adds it in id order like RPG Maker writes them.
+pub fn set(chunks: &mut Array<Chunk<UnknownChunk>>, id: u32, bytes: Vec<u8>) {
+    let chunk_id = |chunk: &Chunk<UnknownChunk>| {
+        let UnknownChunk::Unknown { id, .. } = &chunk.data;
+        *id
+    };
+    let chunk = Chunk::from(UnknownChunk::Unknown { id, bytes });
+    match chunks
+        .inner_vec
+        .iter()
+        .position(|chunk| chunk_id(chunk) >= id)
+    {
+        Some(position) if chunk_id(&chunks.inner_vec[position]) == id => {
+            chunks.inner_vec[position] = chunk;
+        }
+        Some(position) => chunks.inner_vec.insert(position, chunk),
+        None => chunks.inner_vec.push(chunk),
+    }
+}
+
+/// Removes the chunk `id`, if any.
+pub fn remove(chunks: &mut Array<Chunk<UnknownChunk>>, id: u32) {
+    chunks.inner_vec.retain(|chunk| {
+        let UnknownChunk::Unknown { id: chunk_id, .. } = &chunk.data;
+        *chunk_id != id
+    });
+}
+
+/// Writes a number as a variable sized number, the way most chunks store them.
+pub fn encode_number(value: u32) -> Vec<u8> {
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    Number(value)
+        .write_le(&mut cursor)
+        .expect("writing to memory can't fail");
+    cursor.into_inner()
+}
+
+pub fn encode_chunks(chunks: &Array<Chunk<UnknownChunk>>) -> Vec<u8> {
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    chunks
+        .write_le(&mut cursor)
+        .expect("writing to memory can't fail");
+    cursor.into_inner()
+}
+
+pub fn encode_records(records: &Array2D<UnknownChunk>) -> Vec<u8> {
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    records
+        .write_le(&mut cursor)
+        .expect("writing to memory can't fail");
+    cursor.into_inner()
+}
+
+/// Reads a chunk that holds nothing but one variable sized number.
+pub fn number(bytes: &[u8]) -> Option<u32> {
+    let mut cursor = std::io::Cursor::new(bytes);
+    let number = Number::read_le(&mut cursor).ok()?;
+    (cursor.position() == bytes.len() as u64).then_some(number.0)
+}
+
+pub fn chunks(bytes: &[u8]) -> Option<Array<Chunk<UnknownChunk>>> {
+    Array::read_le(&mut std::io::Cursor::new(bytes)).ok()
+}
+
+pub fn records(bytes: &[u8]) -> Option<Array2D<UnknownChunk>> {
+    let mut cursor = std::io::Cursor::new(bytes);
+    let records = Array2D::read_le(&mut cursor).ok()?;
+    (cursor.position() == bytes.len() as u64).then_some(records)
+}
+
+/// Reads a chunk of little endian `u16`s, ignoring an odd byte at the end.
+pub fn shorts(bytes: &[u8]) -> Vec<u16> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect()
+}
+
+/// The value of a chunk of a plain kind, or `None` when the bytes don't fit it or the kind has
+/// nested chunks.
+pub fn value(kind: Kind, bytes: &[u8], encoding: crate::code_page::CodePage) -> Option<String> {
+    Some(match kind {
+        Kind::Text => encoding.to_encoding().decode(bytes).0.to_string(),
+        // Negative numbers are stored as their 32 bit two's complement.
+        Kind::Number => (number(bytes)? as i32).to_string(),
+        Kind::Bool => (number(bytes)? != 0).to_string(),
+        Kind::Bytes => format!("{bytes:?}"),
+        Kind::Shorts if bytes.len().is_multiple_of(2) => format!("{:?}", shorts(bytes)),
+        Kind::Longs if bytes.len().is_multiple_of(4) => format!(
+            "{:?}",
+            bytes
+                .chunks_exact(4)
+                .map(|quad| u32::from_le_bytes([quad[0], quad[1], quad[2], quad[3]]))
+                .collect::<Vec<_>>()
+        ),
+        Kind::Double if bytes.len() == 8 => f64::from_le_bytes(bytes.try_into().ok()?).to_string(),
+        Kind::Shorts | Kind::Longs | Kind::Double | Kind::Struct(_) | Kind::Records(_) => {
+            return None;
+        }
+    })
+}
+
+/// The name a record goes by: its text field 1, which every database record uses for its name.
+pub fn record_name(
+    chunks: &Array<Chunk<UnknownChunk>>,
+    fields: &[Field],
+    encoding: crate::code_page::CodePage,
+) -> Option<String> {
+    let Some(Field {
+        kind: Kind::Text, ..
+    }) = find(fields, 1)
+    else {
+        return None;
+    };
+    get(chunks, 1).map(|bytes| encoding.to_encoding().decode(bytes).0.to_string())
+}
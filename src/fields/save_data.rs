@@ -0,0 +1,306 @@
+//! Chunks of a save file, as RPG Maker 2000 and 2003 write them.
+
+use super::{
+    Field,
+    Kind::{Bool, Bytes, Double, Longs, Number, Records, Shorts, Struct, Text},
+    field,
+};
+
+pub const TITLE: &[Field] = &[
+    field(1, "Timestamp", Double),
+    field(11, "Leader Name", Text),
+    field(12, "Leader Level", Number),
+    field(13, "Leader HP", Number),
+    field(21, "Face 1", Text),
+    field(22, "Face 1 Index", Number),
+    field(23, "Face 2", Text),
+    field(24, "Face 2 Index", Number),
+    field(25, "Face 3", Text),
+    field(26, "Face 3 Index", Number),
+    field(27, "Face 4", Text),
+    field(28, "Face 4 Index", Number),
+];
+
+pub const SCREEN: &[Field] = &[
+    field(1, "Tint Target Red", Number),
+    field(2, "Tint Target Green", Number),
+    field(3, "Tint Target Blue", Number),
+    field(4, "Tint Target Saturation", Number),
+    field(11, "Tint Red", Double),
+    field(12, "Tint Green", Double),
+    field(13, "Tint Blue", Double),
+    field(14, "Tint Saturation", Double),
+    field(15, "Tint Frames Left", Number),
+    field(20, "Flash Continuous", Bool),
+    field(21, "Flash Red", Number),
+    field(22, "Flash Green", Number),
+    field(23, "Flash Blue", Number),
+    field(24, "Flash Level", Double),
+    field(25, "Flash Frames Left", Number),
+    field(30, "Shake Continuous", Bool),
+    field(31, "Shake Strength", Number),
+    field(32, "Shake Speed", Number),
+    field(33, "Shake X", Number),
+    field(34, "Shake Y", Number),
+    field(35, "Shake Frames Left", Number),
+    field(41, "Pan X", Number),
+    field(42, "Pan Y", Number),
+    field(43, "Battle Animation", Number),
+    field(44, "Battle Animation Target", Number),
+    field(45, "Battle Animation Frame", Number),
+    field(46, "Battle Animation Active", Bool),
+    field(47, "Battle Animation Global", Bool),
+    field(48, "Weather", Number),
+    field(49, "Weather Strength", Number),
+];
+
+pub const PICTURE: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Start X", Double),
+    field(3, "Start Y", Double),
+    field(4, "X", Double),
+    field(5, "Y", Double),
+    field(6, "Scrolls with Map", Bool),
+    field(7, "Magnification", Double),
+    field(8, "Top Transparency", Double),
+    field(9, "Transparent Color", Bool),
+    field(11, "Red", Double),
+    field(12, "Green", Double),
+    field(13, "Blue", Double),
+    field(14, "Saturation", Double),
+    field(15, "Effect", Number),
+    field(16, "Effect Power", Double),
+    field(17, "Bottom Transparency", Double),
+    field(18, "Sheet Columns", Number),
+    field(19, "Sheet Rows", Number),
+    field(20, "Sheet Frame", Number),
+    field(21, "Sheet Speed", Number),
+    field(22, "Frames", Number),
+    field(23, "Sheet Plays Once", Bool),
+    field(24, "Map Layer", Number),
+    field(25, "Battle Layer", Number),
+    field(26, "Flags", Number),
+    field(31, "Target X", Double),
+    field(32, "Target Y", Double),
+    field(33, "Target Magnification", Double),
+    field(34, "Target Top Transparency", Double),
+    field(35, "Target Bottom Transparency", Double),
+    field(41, "Target Red", Double),
+    field(42, "Target Green", Double),
+    field(43, "Target Blue", Double),
+    field(44, "Target Saturation", Double),
+    field(46, "Target Effect Power", Double),
+    field(51, "Frames Left", Number),
+    field(52, "Rotation", Double),
+    field(53, "Waver", Number),
+];
+
+pub const MOVE_ROUTE: &[Field] = &[
+    field(11, "Commands Size", Number),
+    field(12, "Commands", Bytes),
+    field(21, "Repeat", Bool),
+    field(22, "Skippable", Bool),
+];
+
+/// The fields of the party, vehicles and map events, followed by those of one of them.
+macro_rules! character {
+    ($($field:expr),* $(,)?) => {
+        &[
+            field(1, "Active", Bool),
+            field(11, "Map", Number),
+            field(12, "X", Number),
+            field(13, "Y", Number),
+            field(21, "Direction", Number),
+            field(22, "Facing", Number),
+            field(23, "Animation Frame", Number),
+            field(24, "Transparency", Number),
+            field(31, "Steps Left", Number),
+            field(32, "Move Frequency", Number),
+            field(33, "Layer", Number),
+            field(34, "Overlap Forbidden", Bool),
+            field(35, "Animation Type", Number),
+            field(36, "Lock Facing", Bool),
+            field(37, "Move Speed", Number),
+            field(41, "Move Route", Struct(MOVE_ROUTE)),
+            field(42, "Move Route Overwritten", Bool),
+            field(43, "Move Route Index", Number),
+            field(44, "Move Route Finished", Bool),
+            field(46, "Hidden", Bool),
+            field(47, "Move Route Through", Bool),
+            field(48, "Animation Count", Number),
+            field(49, "Max Stop Count", Number),
+            field(50, "Jumping", Bool),
+            field(51, "Jump Start X", Number),
+            field(52, "Jump Start Y", Number),
+            field(54, "Paused", Bool),
+            field(55, "Flying", Bool),
+            field(56, "Charset", Text),
+            field(57, "Charset Index", Number),
+            field(58, "Processed", Bool),
+            field(61, "Flash Red", Number),
+            field(62, "Flash Green", Number),
+            field(63, "Flash Blue", Number),
+            field(64, "Flash Level", Double),
+            field(65, "Flash Frames Left", Number),
+            $($field),*
+        ]
+    };
+}
+
+pub const PARTY_LOCATION: &[Field] = character![
+    field(101, "Boarding", Bool),
+    field(102, "Aboard", Bool),
+    field(103, "Vehicle", Number),
+    field(104, "Unboarding", Bool),
+    field(105, "Move Speed Before Boarding", Number),
+    field(108, "Menu Called", Bool),
+    field(111, "Pan State", Number),
+    field(112, "Pan X", Number),
+    field(113, "Pan Y", Number),
+    field(114, "Pan Target X", Number),
+    field(115, "Pan Target Y", Number),
+    field(121, "Pan Speed", Number),
+    field(124, "Encounter Steps", Number),
+    field(125, "Encounter Called", Bool),
+    field(131, "Map Save Count", Number),
+    field(132, "Database Save Count", Number),
+];
+
+pub const VEHICLE_LOCATION: &[Field] = character![
+    field(101, "Vehicle", Number),
+    field(102, "Ascent Left", Number),
+    field(103, "Descent Left", Number),
+    field(111, "Original Charset", Text),
+    field(112, "Original Charset Index", Number),
+];
+
+pub const ACTOR: &[Field] = &[
+    field(1, "Name", Text),
+    field(2, "Title", Text),
+    field(11, "Charset", Text),
+    field(12, "Charset Index", Number),
+    field(13, "Transparent", Bool),
+    field(21, "Faceset", Text),
+    field(22, "Faceset Index", Number),
+    field(31, "Level", Number),
+    field(32, "Experience", Number),
+    field(33, "Max HP Bonus", Number),
+    field(34, "Max SP Bonus", Number),
+    field(41, "Attack Bonus", Number),
+    field(42, "Defense Bonus", Number),
+    field(43, "Spirit Bonus", Number),
+    field(44, "Agility Bonus", Number),
+    field(51, "Skills Size", Number),
+    field(52, "Skills", Shorts),
+    field(61, "Equipment", Shorts),
+    field(71, "HP", Number),
+    field(72, "SP", Number),
+    field(80, "Battle Commands", Longs),
+    field(81, "States Size", Number),
+    field(82, "States", Shorts),
+    field(83, "Battle Commands Changed", Bool),
+    field(90, "Class", Number),
+    field(91, "Row", Number),
+    field(92, "Two Weapons", Bool),
+    field(93, "Lock Equipment", Bool),
+    field(94, "Auto Battle", Bool),
+    field(95, "Strong Defense", Bool),
+    field(96, "Battler Animation", Number),
+];
+
+pub const INVENTORY: &[Field] = &[
+    field(1, "Party Size", Number),
+    field(2, "Party", Shorts),
+    field(11, "Items Size", Number),
+    field(12, "Items", Shorts),
+    field(13, "Item Counts", Bytes),
+    field(14, "Item Uses", Bytes),
+    field(21, "Gold", Number),
+    field(23, "Timer 1 Frames", Number),
+    field(24, "Timer 1 Active", Bool),
+    field(25, "Timer 1 Visible", Bool),
+    field(26, "Timer 1 in Battle", Bool),
+    field(27, "Timer 2 Frames", Number),
+    field(28, "Timer 2 Active", Bool),
+    field(29, "Timer 2 Visible", Bool),
+    field(30, "Timer 2 in Battle", Bool),
+    field(32, "Battles", Number),
+    field(33, "Defeats", Number),
+    field(34, "Escapes", Number),
+    field(35, "Victories", Number),
+    field(41, "Turns", Number),
+    field(42, "Steps", Number),
+];
+
+pub const TARGET: &[Field] = &[
+    field(1, "Map", Number),
+    field(2, "X", Number),
+    field(3, "Y", Number),
+    field(4, "Set Switch", Bool),
+    field(5, "Switch", Number),
+];
+
+pub const EXECUTION_FRAME: &[Field] = &[
+    field(1, "Commands Size", Number),
+    field(2, "Commands", Bytes),
+    field(11, "Current Command", Number),
+    field(12, "Event", Number),
+    field(13, "Triggered by Action Button", Bool),
+    field(21, "Branch Path Size", Number),
+    field(22, "Branch Path", Bytes),
+];
+
+pub const EXECUTION_STATE: &[Field] = &[
+    field(1, "Stack", Records(EXECUTION_FRAME)),
+    field(4, "Showing Message", Bool),
+    field(5, "Abort on Escape", Bool),
+    field(6, "Waiting for Movement", Bool),
+    field(7, "Waiting for Key", Bool),
+    field(8, "Key Variable", Number),
+    field(9, "Key Directions", Bool),
+    field(10, "Key Decision", Bool),
+    field(11, "Key Cancel", Bool),
+    field(12, "Key Shift / Numbers", Bool),
+    field(13, "Key Down / Operators", Bool),
+    field(14, "Key Left / Shift", Bool),
+    field(15, "Key Right", Bool),
+    field(16, "Key Up", Bool),
+    field(21, "Wait Frames", Number),
+    field(22, "Key Time Variable", Number),
+    field(23, "Key Down", Bool),
+    field(24, "Key Left", Bool),
+    field(25, "Key Right", Bool),
+    field(26, "Key Up", Bool),
+    field(27, "Key Timed", Bool),
+    field(28, "Waiting for Enter", Bool),
+];
+
+pub const MAP_EVENT: &[Field] = character![
+    field(101, "Waiting to Run", Bool),
+    field(102, "Original Move Route Index", Number),
+    field(103, "Triggered by Action Button", Bool),
+    field(108, "Parallel Process", Struct(EXECUTION_STATE)),
+];
+
+pub const MAP_INFO: &[Field] = &[
+    field(1, "Pan X", Number),
+    field(2, "Pan Y", Number),
+    field(3, "Encounter Rate", Number),
+    field(5, "Chipset", Number),
+    field(11, "Events", Records(MAP_EVENT)),
+    field(21, "Lower Tile Substitutions", Bytes),
+    field(22, "Upper Tile Substitutions", Bytes),
+    field(32, "Panorama", Text),
+    field(33, "Panorama Horizontal Loop", Bool),
+    field(34, "Panorama Vertical Loop", Bool),
+    field(35, "Panorama Horizontal Autoscroll", Bool),
+    field(36, "Panorama Horizontal Speed", Number),
+    field(37, "Panorama Vertical Autoscroll", Bool),
+    field(38, "Panorama Vertical Speed", Number),
+];
+
+pub const PANORAMA: &[Field] = &[field(1, "X", Number), field(2, "Y", Number)];
+
+pub const COMMON_EVENT: &[Field] = &[field(1, "Parallel Process", Struct(EXECUTION_STATE))];
+
+pub const EASY_RPG: &[Field] = &[field(1, "Version", Number), field(2, "Code Page", Number)];
@@ -0,0 +1,347 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use lcf::raw::{lmt::start::StartChunk, lmu::event::instruction::Instruction};
+
+use crate::project::{Location, Project};
+
+/// A transfer from one map to another.
+pub struct Edge {
+    /// The command that performs the transfer.
+    pub location: Location,
+    /// `None` for common events, which can run on any map.
+    pub from: Option<u32>,
+    pub to: u32,
+    pub x: u32,
+    pub y: u32,
+    pub kind: Kind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Teleport,
+    Vehicle(u32),
+}
+
+impl Edge {
+    /// The event or common event performing the transfer.
+    pub fn source(&self) -> String {
+        match self.location {
+            Location::Event { event, .. } => format!("E{event:04}"),
+            Location::CommonEvent { id, .. } => format!("CE{id:04}"),
+            Location::Map { .. } | Location::MapTree { .. } => String::new(),
+        }
+    }
+}
+
+impl Kind {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Kind::Teleport => "Teleport",
+            Kind::Vehicle(0) => "Boat",
+            Kind::Vehicle(1) => "Ship",
+            Kind::Vehicle(2) => "Airship",
+            Kind::Vehicle(_) => "Vehicle",
+        }
+    }
+}
+
+/// How the maps of a project connect to each other.
+pub struct Graph {
+    pub maps: Vec<u32>,
+    pub edges: Vec<Edge>,
+    pub start: Option<u32>,
+}
+
+impl Graph {
+    pub fn build(project: &Project) -> Self {
+        let mut edges = Vec::new();
+        for (location, command) in project.commands() {
+            let from = match location {
+                Location::Event { map, .. } => Some(map),
+                _ => None,
+            };
+            let (to, x, y, kind) = match command.instruction {
+                Instruction::TransferPlayer { map, x, y, .. } => (map, x, y, Kind::Teleport),
+                Instruction::SetVehicleLocation {
+                    vehicle,
+                    operand: 0,
+                    map,
+                    x,
+                    y,
+                } => (map, x, y, Kind::Vehicle(vehicle)),
+                _ => continue,
+            };
+            edges.push(Edge {
+                location,
+                from,
+                to,
+                x,
+                y,
+                kind,
+            });
+        }
+
+        let mut maps: BTreeSet<u32> = project.maps.iter().map(|map| map.id).collect();
+        maps.extend(
+            edges
+                .iter()
+                .flat_map(|edge| edge.from.into_iter().chain([edge.to])),
+        );
+
+        let start = project.map_tree.as_ref().and_then(|map_tree| {
+            map_tree
+                .start
+                .inner_vec
+                .iter()
+                .find_map(|chunk| match chunk.data {
+                    StartChunk::PartyMapID(map) => Some(map.0),
+                    _ => None,
+                })
+        });
+
+        Self {
+            maps: maps.into_iter().collect(),
+            edges,
+            start,
+        }
+    }
+
+    /// Maps no chain of transfers from the starting map leads to. Common events can run
+    /// anywhere, so their transfers count once any map is reached. Without a starting map,
+    /// nothing is reported.
+    pub fn unreachable(&self) -> Vec<u32> {
+        let Some(start) = self.start else {
+            return Vec::new();
+        };
+        let mut reached = BTreeSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let common = self.edges.iter().filter(|edge| edge.from.is_none());
+        for edge in common {
+            if reached.insert(edge.to) {
+                queue.push_back(edge.to);
+            }
+        }
+        while let Some(map) = queue.pop_front() {
+            for edge in self.edges.iter().filter(|edge| edge.from == Some(map)) {
+                if reached.insert(edge.to) {
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        self.maps
+            .iter()
+            .copied()
+            .filter(|map| !reached.contains(map))
+            .collect()
+    }
+
+    /// Whether there is no transfer back from `edge.to` to `edge.from`.
+    pub fn is_one_way(&self, edge: &Edge) -> bool {
+        edge.from.is_some_and(|from| {
+            from != edge.to
+                && !self
+                    .edges
+                    .iter()
+                    .any(|other| other.from == Some(edge.to) && other.to == from)
+        })
+    }
+
+    /// Writes the graph in Graphviz DOT format.
+    pub fn to_dot(&self, project: &Project, encoding: crate::code_page::CodePage) -> String {
+        let mut dot = String::from("digraph maps {\n");
+        let unreachable = self.unreachable();
+        for map in &self.maps {
+            let name = project
+                .map_name(*map)
+                .map(|name| encoding.to_encoding().decode(name).0.into_owned())
+                .unwrap_or_default();
+            let style = if Some(*map) == self.start {
+                ", shape=doublecircle"
+            } else if unreachable.contains(map) {
+                ", color=red"
+            } else {
+                ""
+            };
+            dot += &format!(
+                "    map{map} [label=\"Map{map:04}\\n{}\"{style}];\n",
+                escape(&name)
+            );
+        }
+        if self.edges.iter().any(|edge| edge.from.is_none()) {
+            dot += "    common [label=\"Common Events\", shape=box];\n";
+        }
+
+        for edge in &self.edges {
+            let from = edge
+                .from
+                .map_or_else(|| "common".to_string(), |map| format!("map{map}"));
+            let source = edge.source();
+            let style = match edge.kind {
+                Kind::Teleport => "",
+                Kind::Vehicle(_) => ", style=dashed",
+            };
+            dot += &format!(
+                "    {from} -> map{} [label=\"{source} {} ({}, {})\"{style}];\n",
+                edge.to,
+                edge.kind.to_str(),
+                edge.x,
+                edge.y
+            );
+        }
+        dot += "}\n";
+        dot
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Node positions of the interactive graph, kept between frames so nodes can be dragged.
+#[derive(Default)]
+pub struct Layout {
+    positions: HashMap<Option<u32>, egui::Pos2>,
+}
+
+const NODE_SIZE: egui::Vec2 = egui::vec2(96.0, 32.0);
+const SPACING: egui::Vec2 = egui::vec2(160.0, 80.0);
+
+/// Draws the graph, returning a location if a node or an edge was clicked.
+pub fn show(
+    ui: &mut egui::Ui,
+    project: &Project,
+    graph: &Graph,
+    layout: &mut Layout,
+    encoding: crate::code_page::CodePage,
+) -> Option<Location> {
+    let columns = (graph.maps.len() as f32).sqrt().ceil().max(1.0) as usize;
+    let nodes: Vec<Option<u32>> = graph
+        .maps
+        .iter()
+        .copied()
+        .map(Some)
+        .chain(
+            graph
+                .edges
+                .iter()
+                .any(|edge| edge.from.is_none())
+                .then_some(None),
+        )
+        .collect();
+    for (index, node) in nodes.iter().enumerate() {
+        layout.positions.entry(*node).or_insert_with(|| {
+            egui::pos2(
+                (index % columns) as f32 * SPACING.x,
+                (index / columns) as f32 * SPACING.y,
+            )
+        });
+    }
+
+    let size = nodes
+        .iter()
+        .map(|node| layout.positions[node].to_vec2() + NODE_SIZE)
+        .fold(egui::Vec2::ZERO, egui::Vec2::max);
+
+    let mut clicked = None;
+    egui::ScrollArea::both().show(ui, |ui| {
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+        let origin = rect.min.to_vec2();
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals().clone();
+        let unreachable = graph.unreachable();
+
+        for edge in &graph.edges {
+            let from = layout.positions[&edge.from] + origin + NODE_SIZE / 2.0;
+            let to = layout.positions[&Some(edge.to)] + origin + NODE_SIZE / 2.0;
+            let color = if graph.is_one_way(edge) {
+                visuals.warn_fg_color
+            } else {
+                visuals.text_color()
+            };
+            let label = format!("{} ({}, {})", edge.source(), edge.x, edge.y);
+            let font = egui::FontId::proportional(10.0);
+            if from == to {
+                let center = from - egui::vec2(0.0, NODE_SIZE.y);
+                painter.circle_stroke(center, 8.0, (1.0, color));
+                let top = center - egui::vec2(0.0, 8.0);
+                painter.text(top, egui::Align2::CENTER_BOTTOM, label, font, color);
+                continue;
+            }
+            let direction = (to - from).normalized();
+            painter.arrow(
+                from,
+                to - from - direction * NODE_SIZE.x / 2.0,
+                egui::Stroke::new(1.0, color),
+            );
+            // Halfway along the edge, so edges both ways between two maps keep their labels apart
+            // by the side of the arrow they are on.
+            let middle = from + (to - from) / 2.0 + direction.rot90() * 8.0;
+            painter.text(middle, egui::Align2::CENTER_CENTER, label, font, color);
+        }
+
+        for node in &nodes {
+            let position = layout.positions[node] + origin;
+            let node_rect = egui::Rect::from_min_size(position, NODE_SIZE);
+            let id = ui.id().with(("graph node", node));
+            let response = ui.interact(node_rect, id, egui::Sense::click_and_drag());
+            if response.dragged() {
+                *layout.positions.get_mut(node).unwrap() += response.drag_delta();
+            }
+
+            let stroke = match node {
+                Some(map) if unreachable.contains(map) => visuals.error_fg_color,
+                Some(map) if Some(*map) == graph.start => visuals.selection.stroke.color,
+                _ => visuals.text_color(),
+            };
+            painter.rect(
+                node_rect,
+                4.0,
+                visuals.extreme_bg_color,
+                egui::Stroke::new(1.0, stroke),
+                egui::StrokeKind::Inside,
+            );
+            let label = match node {
+                Some(map) => format!("Map{map:04}"),
+                None => "Common Events".to_string(),
+            };
+            painter.text(
+                node_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                label,
+                egui::FontId::default(),
+                visuals.text_color(),
+            );
+
+            let response = match node {
+                Some(map) => response.on_hover_text(
+                    project
+                        .map_name(*map)
+                        .map(|name| encoding.to_encoding().decode(name).0.into_owned())
+                        .unwrap_or_default(),
+                ),
+                None => response,
+            };
+            if response.clicked()
+                && let Some(map) = node
+            {
+                clicked = Some(Location::Map { map: *map });
+            }
+            response.context_menu(|ui| {
+                for edge in graph.edges.iter().filter(|edge| edge.from == *node) {
+                    let text = format!(
+                        "{} to Map{:04} ({}, {})",
+                        edge.kind.to_str(),
+                        edge.to,
+                        edge.x,
+                        edge.y
+                    );
+                    if ui.button(text).clicked() {
+                        clicked = Some(edge.location);
+                        ui.close();
+                    }
+                }
+            });
+        }
+    });
+    clicked
+}
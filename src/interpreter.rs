@@ -0,0 +1,230 @@
+//! The event interpreters of a save: which events were running when the game was saved, and
+//! which command each of them was at.
+
+use std::io::Cursor;
+
+use binrw::BinRead;
+use lcf::{
+    helpers::{Array, Chunk, UnknownChunk},
+    raw::{
+        lmu::event::command::Command,
+        lsd::{LcfSaveDataChunk, RawLcfSaveData},
+    },
+};
+
+use crate::{
+    fields::save_data,
+    project::{Location, Project},
+    views::commands::Category,
+};
+
+/// What an interpreter runs for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Owner {
+    /// Autorun events, action button and touch events, and calls from them.
+    Foreground,
+    /// The parallel process of an event on the current map.
+    MapEvent(u32),
+    /// The parallel process of a common event.
+    CommonEvent(u32),
+}
+
+/// One event on an interpreter's call stack. Called events sit above their callers.
+pub struct Frame {
+    /// The map event being run, or 0 for a common event.
+    pub event: u32,
+    /// The command the interpreter is at.
+    pub current: usize,
+    /// A copy of the page's or common event's commands, made when the event started.
+    pub commands: Vec<Command>,
+}
+
+pub struct Stack {
+    pub owner: Owner,
+    pub frames: Vec<Frame>,
+    /// The interpreter's fields besides the stack, like what it is waiting for.
+    pub state: Array<Chunk<UnknownChunk>>,
+}
+
+/// Every interpreter with events on its stack, the foreground one first.
+pub fn stacks(save: &RawLcfSaveData) -> Vec<Stack> {
+    let mut stacks = Vec::new();
+    for chunk in &save.0.inner_vec {
+        match &chunk.data {
+            LcfSaveDataChunk::ExecutionState { bytes } => {
+                stacks.extend(
+                    crate::fields::chunks(bytes).and_then(|state| stack(Owner::Foreground, state)),
+                );
+            }
+            LcfSaveDataChunk::MapInfo { bytes } => {
+                let Some(info) = crate::fields::chunks(bytes) else {
+                    continue;
+                };
+                let events = crate::fields::get(&info, 11).and_then(crate::fields::records);
+                for (id, event) in events.map_or_default(|events| events.inner_vec) {
+                    let state = crate::fields::get(&event, 108).and_then(crate::fields::chunks);
+                    stacks.extend(state.and_then(|state| stack(Owner::MapEvent(id.0), state)));
+                }
+            }
+            LcfSaveDataChunk::CommonEvents { bytes } => {
+                let events = crate::fields::records(bytes);
+                for (id, event) in events.map_or_default(|events| events.inner_vec) {
+                    let state = crate::fields::get(&event, 1).and_then(crate::fields::chunks);
+                    stacks.extend(state.and_then(|state| stack(Owner::CommonEvent(id.0), state)));
+                }
+            }
+            _ => (),
+        }
+    }
+    stacks
+}
+
+fn stack(owner: Owner, state: Array<Chunk<UnknownChunk>>) -> Option<Stack> {
+    let number = |chunks: &Array<Chunk<UnknownChunk>>, id| {
+        crate::fields::get(chunks, id)
+            .and_then(crate::fields::number)
+            .unwrap_or_default()
+    };
+    let frames = crate::fields::get(&state, 1)
+        .and_then(crate::fields::records)
+        .map_or_default(|frames| {
+            frames
+                .inner_vec
+                .iter()
+                .map(|(_, frame)| Frame {
+                    event: number(frame, 12),
+                    current: number(frame, 11) as usize,
+                    commands: crate::fields::get(frame, 2).map_or_default(commands),
+                })
+                .collect::<Vec<_>>()
+        });
+    (!frames.is_empty()).then_some(Stack {
+        owner,
+        frames,
+        state,
+    })
+}
+
+/// Decodes event commands up to the zero that ends them, or as far as they parse.
+fn commands(bytes: &[u8]) -> Vec<Command> {
+    let mut cursor = Cursor::new(bytes);
+    let mut commands = Vec::new();
+    loop {
+        let rest = &bytes[cursor.position() as usize..];
+        if rest.len() < 4 || rest[..4] == [0; 4] {
+            break;
+        }
+        match Command::read_le(&mut cursor) {
+            Ok(command) => commands.push(command),
+            Err(_) => break,
+        }
+    }
+    commands
+}
+
+/// The page or common event a frame runs, found by its commands, pointing at `command`.
+///
+/// `map` is the map the party was on, whose events are the only ones a frame can be from.
+pub fn locate(project: &Project, map: u32, frame: &Frame, command: usize) -> Option<Location> {
+    let page = project.map(map).and_then(|data| {
+        data.unit
+            .events
+            .iter()
+            .filter(|event| event.id == frame.event)
+            .find_map(|event| {
+                let page = event
+                    .pages
+                    .iter()
+                    .position(|page| page.commands == frame.commands)?;
+                Some(Location::Event {
+                    map,
+                    event: event.id,
+                    page,
+                    command: Some(command),
+                })
+            })
+    });
+    page.or_else(|| {
+        project
+            .common_events
+            .iter()
+            .find(|event| event.commands == frame.commands)
+            .map(|event| Location::CommonEvent {
+                id: event.id,
+                command: Some(command),
+            })
+    })
+}
+
+/// Draws each interpreter's stack with the commands of its frames, returning the command that
+/// was clicked.
+pub fn show(
+    ui: &mut egui::Ui,
+    save: &RawLcfSaveData,
+    project: Option<&Project>,
+    encoding: crate::code_page::CodePage,
+) -> Option<Location> {
+    let stacks = stacks(save);
+    if stacks.is_empty() {
+        ui.label("No event was running when the game was saved.");
+        return None;
+    }
+    let map = crate::save::party_location(save)
+        .and_then(|chunks| crate::fields::get(&chunks, 11).and_then(crate::fields::number))
+        .unwrap_or_default();
+
+    let mut clicked = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (index, stack) in stacks.iter().enumerate() {
+            let title = match stack.owner {
+                Owner::Foreground => "Foreground".to_string(),
+                Owner::MapEvent(id) => format!("E{id:04} parallel process"),
+                Owner::CommonEvent(id) => format!("Common Event {id:04} parallel process"),
+            };
+            egui::CollapsingHeader::new(title)
+                .id_salt(("interpreter", index))
+                .default_open(stack.owner == Owner::Foreground)
+                .show(ui, |ui| {
+                    for chunk in &stack.state.inner_vec {
+                        let UnknownChunk::Unknown { id, bytes } = &chunk.data;
+                        if let Some(field) = crate::fields::find(save_data::EXECUTION_STATE, *id)
+                            && let Some(value) = crate::fields::value(field.kind, bytes, encoding)
+                        {
+                            ui.label(format!("{}: {value}", field.name));
+                        }
+                    }
+
+                    for (depth, frame) in stack.frames.iter().enumerate() {
+                        let locate = |command| {
+                            project.and_then(|project| locate(project, map, frame, command))
+                        };
+                        let title = match (project, locate(frame.current)) {
+                            (Some(project), Some(location)) => project.describe(location, encoding),
+                            _ => format!("E{:04}, command {}", frame.event, frame.current),
+                        };
+                        // The innermost frame is the one the interpreter is stuck in.
+                        egui::CollapsingHeader::new(format!("#{depth} {title}"))
+                            .id_salt(("interpreter", index, depth))
+                            .default_open(depth + 1 == stack.frames.len())
+                            .show(ui, |ui| {
+                                for (command_index, command) in frame.commands.iter().enumerate() {
+                                    let current = command_index == frame.current;
+                                    let label = egui::RichText::new(format!(
+                                        "{} {command_index}: {}{:?} {}",
+                                        if current { "▶" } else { " " },
+                                        "  ".repeat(command.indent as usize),
+                                        command.instruction,
+                                        encoding.to_encoding().decode(&command.string).0,
+                                    ))
+                                    .color(Category::of(&command.instruction).color());
+                                    if ui.selectable_label(current, label).clicked() {
+                                        clicked = locate(command_index);
+                                    }
+                                }
+                            });
+                    }
+                });
+        }
+    });
+    clicked
+}
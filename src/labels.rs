@@ -0,0 +1,28 @@
+//! Labels for lcf's enums and for ids named in the database, shared by the views.
+
+use lcf::enums::Trigger;
+
+pub const fn trigger(trigger: Trigger) -> &'static str {
+    match trigger {
+        Trigger::ActionButton => "Action Button",
+        Trigger::PlayerTouch => "Player Touch",
+        Trigger::EventTouch => "Event Touch",
+        Trigger::Autorun => "Autorun",
+        Trigger::Parallel => "Parallel process",
+    }
+}
+
+/// A switch, variable or database record as the editor lists it, like `S0012 Boss Defeated`.
+pub fn named(
+    prefix: &str,
+    id: u32,
+    name: Option<&[u8]>,
+    encoding: crate::code_page::CodePage,
+) -> String {
+    match name {
+        Some(name) if !name.is_empty() => {
+            format!("{prefix}{id:04} {}", encoding.to_encoding().decode(name).0)
+        }
+        _ => format!("{prefix}{id:04}"),
+    }
+}
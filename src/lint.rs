@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, HashMap};
+
+use lcf::{enums::Trigger, raw::lmt::map::MapChunk, raw::lmu::event::instruction::Instruction};
+
+use crate::project::{Location, Project};
+
+pub struct Problem {
+    pub location: Location,
+    pub message: String,
+}
+
+/// Checks a project for references to things that do not exist.
+pub fn check(project: &Project, encoding: crate::code_page::CodePage) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    for map in &project.failed_maps {
+        problems.push(Problem {
+            location: Location::Map { map: map.id },
+            message: format!("Map{:04} could not be read: {}", map.id, map.error),
+        });
+    }
+
+    let chipsets = project.chipset_ids();
+    for map in &project.maps {
+        if !chipsets.contains(&map.unit.chipset) {
+            problems.push(Problem {
+                location: Location::Map { map: map.id },
+                message: format!("Chipset {} does not exist", map.unit.chipset),
+            });
+        }
+    }
+
+    let mut charsets = HashMap::new();
+    for map in &project.maps {
+        for event in &map.unit.events {
+            for (page, data) in event.pages.iter().enumerate() {
+                let file = &data.graphic.file;
+                if file.is_empty() {
+                    continue;
+                }
+                let name = encoding.to_encoding().decode(file).0.into_owned();
+                let found = *charsets
+                    .entry(name.clone())
+                    .or_insert_with(|| project.find_asset("CharSet", &name).is_some());
+                if !found {
+                    problems.push(Problem {
+                        location: Location::Event {
+                            map: map.id,
+                            event: event.id,
+                            page,
+                            command: None,
+                        },
+                        message: format!("Graphic \"{name}\" is missing from CharSet"),
+                    });
+                }
+            }
+        }
+    }
+
+    for (location, command) in project.commands() {
+        let Instruction::TransferPlayer { map, x, y, .. } = command.instruction else {
+            continue;
+        };
+        let message = match project.map(map) {
+            None if project.failed_maps.iter().any(|failed| failed.id == map) => {
+                format!("Teleport to Map{map:04}, which could not be read")
+            }
+            None => format!("Teleport to Map{map:04}, which does not exist"),
+            Some(target) if x >= target.unit.width || y >= target.unit.height => format!(
+                "Teleport to ({x}, {y}) on Map{map:04}, outside its {}×{} area",
+                target.unit.width, target.unit.height
+            ),
+            Some(_) => continue,
+        };
+        problems.push(Problem { location, message });
+    }
+
+    if let Some(map_tree) = &project.map_tree {
+        let parents: BTreeMap<u32, u32> = map_tree
+            .maps
+            .inner_vec
+            .iter()
+            .filter_map(|(id, chunks)| {
+                chunks.inner_vec.iter().find_map(|chunk| match chunk.data {
+                    MapChunk::Parent(parent) => Some((id.0, parent.0)),
+                    _ => None,
+                })
+            })
+            .collect();
+        let exists = |id: u32| map_tree.maps.inner_vec.iter().any(|(map, _)| map.0 == id);
+
+        for (&map, &parent) in &parents {
+            // The root of the tree is the project itself, which is its own parent.
+            if map == 0 {
+                continue;
+            }
+            if parent != 0 && !exists(parent) {
+                problems.push(Problem {
+                    location: Location::MapTree { map },
+                    message: format!("Parent Map{parent:04} does not exist"),
+                });
+                continue;
+            }
+
+            let mut current = parent;
+            for _ in 0..parents.len() {
+                if current == map {
+                    problems.push(Problem {
+                        location: Location::MapTree { map },
+                        message: "Parent chain forms a cycle".to_string(),
+                    });
+                    break;
+                }
+                match parents.get(&current) {
+                    Some(&next) if current != 0 => current = next,
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    let switches = project.switch_count();
+    for event in &project.common_events {
+        if event.state
+            && matches!(event.trigger, Trigger::Autorun | Trigger::Parallel)
+            && (event.switch == 0 || event.switch > switches)
+        {
+            problems.push(Problem {
+                location: Location::CommonEvent {
+                    id: event.id,
+                    command: None,
+                },
+                message: format!(
+                    "Trigger switch {} is outside the {switches} switches in the database",
+                    event.switch
+                ),
+            });
+        }
+    }
+
+    problems
+}
+
+/// Draws the problems, returning a location if one was clicked.
+pub fn show(
+    ui: &mut egui::Ui,
+    project: &Project,
+    problems: &[Problem],
+    encoding: crate::code_page::CodePage,
+) -> Option<Location> {
+    ui.heading(format!("Problems ({})", problems.len()));
+
+    let mut clicked = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("problems").striped(true).show(ui, |ui| {
+            for problem in problems {
+                ui.label(&problem.message);
+                if ui
+                    .link(project.describe(problem.location, encoding))
+                    .clicked()
+                {
+                    clicked = Some(problem.location);
+                }
+                ui.end_row();
+            }
+        });
+    });
+    clicked
+}
@@ -1,10 +1,35 @@
 #![feature(result_option_map_or_default)]
 
 mod app;
+mod assets;
+mod chipset;
+mod cli;
 mod code_page;
+mod export;
+mod fields;
+mod graph;
+mod interpreter;
+mod labels;
+mod lint;
+mod move_route;
+mod project;
+mod references;
+mod render;
+mod report;
+mod save;
+mod save_diff;
+mod save_editor;
+mod sheet;
+mod terms;
+mod tiled;
 mod views;
 
 fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(
         "Lcf explorer",
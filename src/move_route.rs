@@ -0,0 +1,301 @@
+use std::{io::Cursor, ops::Range};
+
+use binrw::BinRead;
+use lcf::{
+    helpers::{Array, Chunk, Number},
+    raw::lmu::{
+        LcfMapUnitChunk, RawLcfMapUnit,
+        event::{EventChunk, move_route::EventMoveRouteChunk, page::EventPageChunk},
+    },
+};
+
+/// A move route with its command bytes decoded.
+#[derive(Clone, Debug, Default)]
+pub struct MoveRoute {
+    pub commands: Vec<MoveCommand>,
+    pub repeat: bool,
+    pub skippable: bool,
+}
+
+impl MoveRoute {
+    pub fn from_chunks(chunks: &Array<Chunk<EventMoveRouteChunk>>) -> Self {
+        let mut route = Self::default();
+        for chunk in &chunks.inner_vec {
+            match &chunk.data {
+                EventMoveRouteChunk::Commands(bytes) => route.commands = decode(bytes),
+                EventMoveRouteChunk::Repeat(val) => route.repeat = val.0 != 0,
+                EventMoveRouteChunk::Skippable(val) => route.skippable = val.0 != 0,
+                EventMoveRouteChunk::CommandsSize(_) | EventMoveRouteChunk::Unknown { .. } => (),
+            }
+        }
+        route
+    }
+
+    /// Finds the custom route of an event page.
+    ///
+    /// The converted map unit drops move routes, so they have to come from the raw one.
+    pub fn find(map_unit: &RawLcfMapUnit, event: u32, page: usize) -> Option<Self> {
+        map_unit
+            .0
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                LcfMapUnitChunk::Events(events) => Some(events),
+                _ => None,
+            })?
+            .inner_vec
+            .iter()
+            .find(|(id, _)| id.0 == event)?
+            .1
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                EventChunk::Pages(pages) => pages.inner_vec.get(page),
+                _ => None,
+            })?
+            .1
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                EventPageChunk::MovementRoute(chunks) => Some(Self::from_chunks(chunks)),
+                _ => None,
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveCommand {
+    MoveUp,
+    MoveRight,
+    MoveDown,
+    MoveLeft,
+    MoveUpRight,
+    MoveDownRight,
+    MoveDownLeft,
+    MoveUpLeft,
+    MoveRandomly,
+    MoveTowardsPlayer,
+    MoveAwayFromPlayer,
+    MoveForward,
+    FaceUp,
+    FaceRight,
+    FaceDown,
+    FaceLeft,
+    TurnRight,
+    TurnLeft,
+    TurnAround,
+    TurnRandomly,
+    FaceRandomDirection,
+    FacePlayer,
+    FaceAwayFromPlayer,
+    Wait,
+    BeginJump,
+    EndJump,
+    LockFacing,
+    UnlockFacing,
+    IncreaseSpeed,
+    DecreaseSpeed,
+    IncreaseFrequency,
+    DecreaseFrequency,
+    SwitchOn(u32),
+    SwitchOff(u32),
+    ChangeGraphic {
+        file: Vec<u8>,
+        index: u32,
+    },
+    PlaySoundEffect {
+        file: Vec<u8>,
+        volume: u32,
+        tempo: u32,
+        balance: u32,
+    },
+    WalkEverywhereOn,
+    WalkEverywhereOff,
+    StopAnimation,
+    StartAnimation,
+    IncreaseTransparency,
+    DecreaseTransparency,
+    Unknown(u32),
+}
+
+impl MoveCommand {
+    pub fn label(&self, encoding: crate::code_page::CodePage) -> String {
+        let name = match self {
+            Self::MoveUp => "Move Up",
+            Self::MoveRight => "Move Right",
+            Self::MoveDown => "Move Down",
+            Self::MoveLeft => "Move Left",
+            Self::MoveUpRight => "Move Up-Right",
+            Self::MoveDownRight => "Move Down-Right",
+            Self::MoveDownLeft => "Move Down-Left",
+            Self::MoveUpLeft => "Move Up-Left",
+            Self::MoveRandomly => "Move Randomly",
+            Self::MoveTowardsPlayer => "Move Towards Player",
+            Self::MoveAwayFromPlayer => "Move Away from Player",
+            Self::MoveForward => "Move Forward",
+            Self::FaceUp => "Face Up",
+            Self::FaceRight => "Face Right",
+            Self::FaceDown => "Face Down",
+            Self::FaceLeft => "Face Left",
+            Self::TurnRight => "Turn 90° Right",
+            Self::TurnLeft => "Turn 90° Left",
+            Self::TurnAround => "Turn 180°",
+            Self::TurnRandomly => "Turn 90° Left or Right",
+            Self::FaceRandomDirection => "Face Random Direction",
+            Self::FacePlayer => "Face Player",
+            Self::FaceAwayFromPlayer => "Face Away from Player",
+            Self::Wait => "Wait",
+            Self::BeginJump => "Begin Jump",
+            Self::EndJump => "End Jump",
+            Self::LockFacing => "Lock Facing",
+            Self::UnlockFacing => "Unlock Facing",
+            Self::IncreaseSpeed => "Increase Movement Speed",
+            Self::DecreaseSpeed => "Decrease Movement Speed",
+            Self::IncreaseFrequency => "Increase Movement Frequency",
+            Self::DecreaseFrequency => "Decrease Movement Frequency",
+            Self::SwitchOn(switch) => return format!("Switch ON: {switch}"),
+            Self::SwitchOff(switch) => return format!("Switch OFF: {switch}"),
+            Self::ChangeGraphic { file, index } => {
+                return format!(
+                    "Change Graphic: {} ({index})",
+                    encoding.to_encoding().decode(file).0
+                );
+            }
+            Self::PlaySoundEffect {
+                file,
+                volume,
+                tempo,
+                balance,
+            } => {
+                return format!(
+                    "Play SE: {} (volume {volume}, tempo {tempo}, balance {balance})",
+                    encoding.to_encoding().decode(file).0
+                );
+            }
+            Self::WalkEverywhereOn => "Walk Everywhere ON",
+            Self::WalkEverywhereOff => "Walk Everywhere OFF",
+            Self::StopAnimation => "Stop Animation",
+            Self::StartAnimation => "Start Animation",
+            Self::IncreaseTransparency => "Increase Transparency",
+            Self::DecreaseTransparency => "Decrease Transparency",
+            Self::Unknown(opcode) => return format!("Unknown command {opcode}"),
+        };
+        name.to_string()
+    }
+}
+
+/// Decodes the command bytes of a move route, stopping at the first truncated command.
+pub fn decode(bytes: &[u8]) -> Vec<MoveCommand> {
+    decode_ranges(bytes)
+        .into_iter()
+        .map(|(command, _)| command)
+        .collect()
+}
+
+/// Like [`decode`], along with the bytes each command was read from.
+pub fn decode_ranges(bytes: &[u8]) -> Vec<(MoveCommand, Range<usize>)> {
+    let mut cursor = Cursor::new(bytes);
+    let mut commands = Vec::new();
+    while (cursor.position() as usize) < bytes.len() {
+        let start = cursor.position() as usize;
+        match decode_command(&mut cursor) {
+            Some(command) => commands.push((command, start..cursor.position() as usize)),
+            None => break,
+        }
+    }
+    commands
+}
+
+fn decode_command(cursor: &mut Cursor<&[u8]>) -> Option<MoveCommand> {
+    let number = |cursor: &mut Cursor<&[u8]>| Number::read_le(cursor).ok().map(|val| val.0);
+    // Unlike strings elsewhere, each byte of these is stored as a number of its own.
+    let string = |cursor: &mut Cursor<&[u8]>| {
+        let length = number(cursor)?;
+        (0..length)
+            .map(|_| number(cursor).map(|byte| byte as u8))
+            .collect::<Option<Vec<_>>>()
+    };
+
+    Some(match number(cursor)? {
+        0 => MoveCommand::MoveUp,
+        1 => MoveCommand::MoveRight,
+        2 => MoveCommand::MoveDown,
+        3 => MoveCommand::MoveLeft,
+        4 => MoveCommand::MoveUpRight,
+        5 => MoveCommand::MoveDownRight,
+        6 => MoveCommand::MoveDownLeft,
+        7 => MoveCommand::MoveUpLeft,
+        8 => MoveCommand::MoveRandomly,
+        9 => MoveCommand::MoveTowardsPlayer,
+        10 => MoveCommand::MoveAwayFromPlayer,
+        11 => MoveCommand::MoveForward,
+        12 => MoveCommand::FaceUp,
+        13 => MoveCommand::FaceRight,
+        14 => MoveCommand::FaceDown,
+        15 => MoveCommand::FaceLeft,
+        16 => MoveCommand::TurnRight,
+        17 => MoveCommand::TurnLeft,
+        18 => MoveCommand::TurnAround,
+        19 => MoveCommand::TurnRandomly,
+        20 => MoveCommand::FaceRandomDirection,
+        21 => MoveCommand::FacePlayer,
+        22 => MoveCommand::FaceAwayFromPlayer,
+        23 => MoveCommand::Wait,
+        24 => MoveCommand::BeginJump,
+        25 => MoveCommand::EndJump,
+        26 => MoveCommand::LockFacing,
+        27 => MoveCommand::UnlockFacing,
+        28 => MoveCommand::IncreaseSpeed,
+        29 => MoveCommand::DecreaseSpeed,
+        30 => MoveCommand::IncreaseFrequency,
+        31 => MoveCommand::DecreaseFrequency,
+        32 => MoveCommand::SwitchOn(number(cursor)?),
+        33 => MoveCommand::SwitchOff(number(cursor)?),
+        34 => MoveCommand::ChangeGraphic {
+            file: string(cursor)?,
+            index: number(cursor)?,
+        },
+        35 => MoveCommand::PlaySoundEffect {
+            file: string(cursor)?,
+            volume: number(cursor)?,
+            tempo: number(cursor)?,
+            balance: number(cursor)?,
+        },
+        36 => MoveCommand::WalkEverywhereOn,
+        37 => MoveCommand::WalkEverywhereOff,
+        38 => MoveCommand::StopAnimation,
+        39 => MoveCommand::StartAnimation,
+        40 => MoveCommand::IncreaseTransparency,
+        41 => MoveCommand::DecreaseTransparency,
+        opcode => MoveCommand::Unknown(opcode),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_each_byte_of_a_string_as_a_number() {
+        // 0x82 doesn't fit in one byte of a variable sized number, so it takes two.
+        let bytes = [34, 3, b'a', 0x81, 0x02, b'c', 2, 0];
+        assert_eq!(
+            decode_ranges(&bytes),
+            [
+                (
+                    MoveCommand::ChangeGraphic {
+                        file: vec![b'a', 0x82, b'c'],
+                        index: 2,
+                    },
+                    0..7,
+                ),
+                (MoveCommand::MoveUp, 7..8),
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_at_a_truncated_command() {
+        let bytes = [23, 35, 4, b'a', b'b'];
+        assert_eq!(decode(&bytes), [MoveCommand::Wait]);
+    }
+}
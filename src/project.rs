@@ -0,0 +1,439 @@
+use std::path::{Path, PathBuf};
+
+use lcf::{
+    helpers::{Array, Chunk},
+    ldb::common_event::CommonEvent,
+    raw::{
+        ldb::{
+            LcfDataBaseChunk, RawLcfDataBase, chipset::ChipSetChunk, switch::SwitchChunk,
+            variable::VariableChunk,
+        },
+        lmt::{RawLcfMapTree, map::MapChunk},
+        lmu::event::command::Command,
+    },
+};
+
+/// A game folder: `RPG_RT.ldb`, `RPG_RT.lmt` and every `MapXXXX.lmu` next to them.
+pub struct Project {
+    pub root: PathBuf,
+    pub database: RawLcfDataBase,
+    pub map_tree: Option<RawLcfMapTree>,
+    pub common_events: Vec<CommonEvent>,
+    pub maps: Vec<Map>,
+    /// Maps that couldn't be read, left out of `maps`.
+    pub failed_maps: Vec<FailedMap>,
+    pub references: crate::references::Index,
+}
+
+pub struct Map {
+    pub id: u32,
+    pub path: PathBuf,
+    pub unit: lcf::lmu::LcfMapUnit,
+}
+
+pub struct FailedMap {
+    pub id: u32,
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Somewhere in the project that can be jumped to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+    CommonEvent {
+        id: u32,
+        command: Option<usize>,
+    },
+    Event {
+        map: u32,
+        event: u32,
+        page: usize,
+        command: Option<usize>,
+    },
+    Map {
+        map: u32,
+    },
+    MapTree {
+        map: u32,
+    },
+}
+
+impl Project {
+    pub fn open(root: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes = std::fs::read(root.join("RPG_RT.ldb"))?;
+        let database = RawLcfDataBase::read(&mut std::io::Cursor::new(bytes))?;
+
+        let map_tree = std::fs::read(root.join("RPG_RT.lmt"))
+            .ok()
+            .and_then(|bytes| RawLcfMapTree::read(&mut std::io::Cursor::new(bytes)).ok());
+
+        let common_events = database
+            .0
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                LcfDataBaseChunk::CommonEvents(events) => Some(events),
+                _ => None,
+            })
+            .map_or_default(|events| {
+                events
+                    .inner_vec
+                    .iter()
+                    .filter_map(|(id, chunks)| {
+                        CommonEvent::default()
+                            .with_id(id.0)
+                            .with_chunks(chunks.clone())
+                            .ok()
+                    })
+                    .collect()
+            });
+
+        let mut maps = Vec::new();
+        let mut failed_maps = Vec::new();
+        for entry in std::fs::read_dir(root)? {
+            let path = entry?.path();
+            let Some(id) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("Map"))
+                .and_then(|name| name.strip_suffix(".lmu"))
+                .and_then(|id| id.parse().ok())
+            else {
+                continue;
+            };
+
+            let unit = std::fs::read(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|bytes| {
+                    lcf::raw::lmu::RawLcfMapUnit::read(&mut std::io::Cursor::new(bytes))
+                        .map_err(|err| err.to_string())
+                })
+                .and_then(|raw| lcf::lmu::LcfMapUnit::try_from(raw).map_err(|err| err.to_string()));
+            match unit {
+                Ok(unit) => maps.push(Map { id, path, unit }),
+                Err(error) => failed_maps.push(FailedMap { id, path, error }),
+            }
+        }
+        maps.sort_by_key(|map| map.id);
+        failed_maps.sort_by_key(|map| map.id);
+
+        let mut project = Self {
+            root: root.to_owned(),
+            database,
+            map_tree,
+            common_events,
+            maps,
+            failed_maps,
+            references: Default::default(),
+        };
+        project.references = crate::references::Index::build(&project);
+        Ok(project)
+    }
+
+    pub fn map_tree_path(&self) -> PathBuf {
+        self.root.join("RPG_RT.lmt")
+    }
+
+    pub fn find_asset(&self, folder: &str, name: &str) -> Option<PathBuf> {
+        crate::assets::find(&self.root, folder, name)
+    }
+
+    fn chipset_chunks(&self, id: u32) -> Option<&Array<Chunk<ChipSetChunk>>> {
+        self.database
+            .0
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                LcfDataBaseChunk::ChipSet(chipsets) => Some(chipsets),
+                _ => None,
+            })?
+            .inner_vec
+            .iter()
+            .find(|(chipset, _)| chipset.0 == id)
+            .map(|(_, chunks)| chunks)
+    }
+
+    /// The image file of a chipset, as stored in the database.
+    pub fn chipset_file(&self, id: u32) -> Option<&[u8]> {
+        self.chipset_chunks(id)?
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                ChipSetChunk::File(bytes) => Some(bytes.as_slice()),
+                _ => None,
+            })
+    }
+
+    pub fn chipset_name(&self, id: u32) -> Option<&[u8]> {
+        self.chipset_chunks(id)?
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                ChipSetChunk::Name(bytes) => Some(bytes.as_slice()),
+                _ => None,
+            })
+    }
+
+    /// Maps drawn with a chipset that place one of its tiles, given by palette index.
+    pub fn maps_using_tile(&self, chipset: u32, upper: bool, index: usize) -> Vec<u32> {
+        self.maps
+            .iter()
+            .filter(|map| map.unit.chipset == chipset)
+            .filter(|map| {
+                if upper {
+                    map.unit
+                        .upper
+                        .iter()
+                        .any(|&tile| crate::chipset::upper_index(tile) == Some(index))
+                } else {
+                    map.unit
+                        .lower
+                        .iter()
+                        .any(|&tile| crate::chipset::lower_index(tile) == Some(index))
+                }
+            })
+            .map(|map| map.id)
+            .collect()
+    }
+
+    /// The passability and terrain tables of a chipset.
+    pub fn chipset(&self, id: u32) -> Option<crate::chipset::ChipSet> {
+        self.chipset_chunks(id)
+            .map(crate::chipset::ChipSet::from_chunks)
+    }
+
+    pub fn chipset_ids(&self) -> Vec<u32> {
+        self.database
+            .0
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                LcfDataBaseChunk::ChipSet(chipsets) => Some(chipsets),
+                _ => None,
+            })
+            .map_or_default(|chipsets| chipsets.inner_vec.iter().map(|(id, _)| id.0).collect())
+    }
+
+    pub fn database_path(&self) -> PathBuf {
+        self.root.join("RPG_RT.ldb")
+    }
+
+    pub fn map(&self, id: u32) -> Option<&Map> {
+        self.maps.iter().find(|map| map.id == id)
+    }
+
+    /// Every event command in the project, common events first.
+    pub fn commands(&self) -> impl Iterator<Item = (Location, &Command)> {
+        let common_events = self.common_events.iter().flat_map(|event| {
+            event.commands.iter().enumerate().map(|(index, command)| {
+                (
+                    Location::CommonEvent {
+                        id: event.id,
+                        command: Some(index),
+                    },
+                    command,
+                )
+            })
+        });
+
+        let maps = self.maps.iter().flat_map(|map| {
+            map.unit.events.iter().flat_map(move |event| {
+                event
+                    .pages
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(page, data)| {
+                        data.commands
+                            .iter()
+                            .enumerate()
+                            .map(move |(index, command)| {
+                                (
+                                    Location::Event {
+                                        map: map.id,
+                                        event: event.id,
+                                        page,
+                                        command: Some(index),
+                                    },
+                                    command,
+                                )
+                            })
+                    })
+            })
+        });
+
+        common_events.chain(maps)
+    }
+
+    pub fn switch_names(&self) -> Vec<(u32, &[u8])> {
+        self.database
+            .0
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                LcfDataBaseChunk::Switches(switches) => Some(switches),
+                _ => None,
+            })
+            .map_or_default(|switches| {
+                switches
+                    .inner_vec
+                    .iter()
+                    .map(|(id, chunks)| {
+                        let name = chunks.inner_vec.iter().find_map(|chunk| match &chunk.data {
+                            SwitchChunk::Name(bytes) => Some(bytes.as_slice()),
+                            SwitchChunk::Unknown { .. } => None,
+                        });
+                        (id.0, name.unwrap_or_default())
+                    })
+                    .collect()
+            })
+    }
+
+    pub fn variable_names(&self) -> Vec<(u32, &[u8])> {
+        self.database
+            .0
+            .inner_vec
+            .iter()
+            .find_map(|chunk| match &chunk.data {
+                LcfDataBaseChunk::Variables(variables) => Some(variables),
+                _ => None,
+            })
+            .map_or_default(|variables| {
+                variables
+                    .inner_vec
+                    .iter()
+                    .map(|(id, chunks)| {
+                        let name = chunks.inner_vec.iter().find_map(|chunk| match &chunk.data {
+                            VariableChunk::Name(bytes) => Some(bytes.as_slice()),
+                            VariableChunk::Unknown { .. } => None,
+                        });
+                        (id.0, name.unwrap_or_default())
+                    })
+                    .collect()
+            })
+    }
+
+    /// How many switches the database holds, numbered from 1.
+    pub fn switch_count(&self) -> u32 {
+        highest_id(&self.switch_names())
+    }
+
+    /// How many variables the database holds, numbered from 1.
+    pub fn variable_count(&self) -> u32 {
+        highest_id(&self.variable_names())
+    }
+
+    pub fn actor_names(&self) -> Vec<(u32, Vec<u8>)> {
+        self.record_names(|chunk| match chunk {
+            LcfDataBaseChunk::Actors(bytes) => Some(bytes),
+            _ => None,
+        })
+    }
+
+    pub fn item_names(&self) -> Vec<(u32, Vec<u8>)> {
+        self.record_names(|chunk| match chunk {
+            LcfDataBaseChunk::Items(bytes) => Some(bytes),
+            _ => None,
+        })
+    }
+
+    /// The parameter curves of an actor: max HP, max SP, attack, defense, spirit and agility,
+    /// one value per level each.
+    pub fn actor_parameters(&self, id: u32) -> Option<Vec<u16>> {
+        let actors = self.records(|chunk| match chunk {
+            LcfDataBaseChunk::Actors(bytes) => Some(bytes),
+            _ => None,
+        })?;
+        let (_, chunks) = actors.inner_vec.iter().find(|(actor, _)| actor.0 == id)?;
+        crate::fields::get(chunks, 31).map(crate::fields::shorts)
+    }
+
+    /// Names of the records in a database chunk lcf keeps as bytes.
+    fn record_names(&self, pick: fn(&LcfDataBaseChunk) -> Option<&Vec<u8>>) -> Vec<(u32, Vec<u8>)> {
+        self.records(pick).map_or_default(|records| {
+            records
+                .inner_vec
+                .iter()
+                .map(|(id, chunks)| {
+                    let name = crate::fields::get(chunks, 1).unwrap_or_default();
+                    (id.0, name.to_vec())
+                })
+                .collect()
+        })
+    }
+
+    fn records(
+        &self,
+        pick: fn(&LcfDataBaseChunk) -> Option<&Vec<u8>>,
+    ) -> Option<lcf::helpers::Array2D<lcf::helpers::UnknownChunk>> {
+        self.database
+            .0
+            .inner_vec
+            .iter()
+            .find_map(|chunk| pick(&chunk.data))
+            .and_then(|bytes| crate::fields::records(bytes))
+    }
+
+    pub fn map_name(&self, id: u32) -> Option<&[u8]> {
+        self.map_tree
+            .as_ref()?
+            .maps
+            .inner_vec
+            .iter()
+            .find_map(|(map, chunks)| {
+                if map.0 != id {
+                    return None;
+                }
+                chunks.inner_vec.iter().find_map(|chunk| match &chunk.data {
+                    MapChunk::Name(bytes) => Some(bytes.as_slice()),
+                    _ => None,
+                })
+            })
+    }
+
+    pub fn describe(&self, location: Location, encoding: crate::code_page::CodePage) -> String {
+        match location {
+            Location::CommonEvent { id, command } => {
+                let name = self
+                    .common_events
+                    .iter()
+                    .find(|event| event.id == id)
+                    .map(|event| encoding.to_encoding().decode(&event.name).0.into_owned())
+                    .unwrap_or_default();
+                match command {
+                    Some(command) => format!("Common Event {id:04}: {name}, command {command}"),
+                    None => format!("Common Event {id:04}: {name}"),
+                }
+            }
+            Location::Event {
+                map,
+                event,
+                page,
+                command,
+            } => {
+                let name = self
+                    .map_name(map)
+                    .map(|name| encoding.to_encoding().decode(name).0.into_owned())
+                    .unwrap_or_default();
+                let page = page + 1;
+                match command {
+                    Some(command) => {
+                        format!("Map{map:04} ({name}) E{event:04} page {page}, command {command}")
+                    }
+                    None => format!("Map{map:04} ({name}) E{event:04} page {page}"),
+                }
+            }
+            Location::Map { map } => {
+                let name = self
+                    .map_name(map)
+                    .map(|name| encoding.to_encoding().decode(name).0.into_owned())
+                    .unwrap_or_default();
+                format!("Map{map:04} ({name})")
+            }
+            Location::MapTree { map } => format!("Map tree entry {map:04}"),
+        }
+    }
+}
+
+fn highest_id(names: &[(u32, &[u8])]) -> u32 {
+    names.iter().map(|(id, _)| *id).max().unwrap_or_default()
+}
@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+
+use lcf::{enums::Trigger, raw::lmu::event::instruction::Instruction};
+
+use crate::project::{Location, Project};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Switch(u32),
+    Variable(u32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Reference {
+    pub location: Location,
+    pub access: Access,
+    pub usage: &'static str,
+}
+
+/// Every read and write of every switch and variable in a project.
+#[derive(Default)]
+pub struct Index {
+    pub switches: BTreeMap<u32, Vec<Reference>>,
+    pub variables: BTreeMap<u32, Vec<Reference>>,
+}
+
+impl Index {
+    pub fn build(project: &Project) -> Self {
+        let mut index = Self::default();
+
+        for event in &project.common_events {
+            if event.state && matches!(event.trigger, Trigger::Autorun | Trigger::Parallel) {
+                index.add(
+                    Target::Switch(event.switch),
+                    Location::CommonEvent {
+                        id: event.id,
+                        command: None,
+                    },
+                    Access::Read,
+                    "Trigger switch",
+                );
+            }
+        }
+
+        for map in &project.maps {
+            for event in &map.unit.events {
+                for (page, data) in event.pages.iter().enumerate() {
+                    let location = Location::Event {
+                        map: map.id,
+                        event: event.id,
+                        page,
+                        command: None,
+                    };
+                    let condition = &data.condition;
+                    if condition.switch_a.0 {
+                        let target = Target::Switch(condition.switch_a.1);
+                        index.add(target, location, Access::Read, "Page condition (Switch A)");
+                    }
+                    if condition.switch_b.0 {
+                        let target = Target::Switch(condition.switch_b.1);
+                        index.add(target, location, Access::Read, "Page condition (Switch B)");
+                    }
+                    if condition.variable.0 {
+                        let target = Target::Variable(condition.variable.1);
+                        index.add(target, location, Access::Read, "Page condition (Variable)");
+                    }
+                }
+            }
+        }
+
+        for (location, command) in project.commands() {
+            for (target, access, usage) in uses(&command.instruction) {
+                index.add(target, location, access, usage);
+            }
+        }
+
+        index
+    }
+
+    fn add(&mut self, target: Target, location: Location, access: Access, usage: &'static str) {
+        let (map, id) = match target {
+            Target::Switch(id) => (&mut self.switches, id),
+            Target::Variable(id) => (&mut self.variables, id),
+        };
+        map.entry(id).or_default().push(Reference {
+            location,
+            access,
+            usage,
+        });
+    }
+
+    pub fn get(&self, target: Target) -> &[Reference] {
+        match target {
+            Target::Switch(id) => self.switches.get(&id),
+            Target::Variable(id) => self.variables.get(&id),
+        }
+        .map_or_default(Vec::as_slice)
+    }
+}
+
+/// The switches and variables an instruction reads or writes.
+fn uses(instruction: &Instruction) -> Vec<(Target, Access, &'static str)> {
+    use Access::{Read, Write};
+    use Target::{Switch, Variable};
+
+    let mut uses = Vec::new();
+    match *instruction {
+        Instruction::InputNumber { variable, .. } => {
+            uses.push((Variable(variable), Write, "Input Number"));
+        }
+        Instruction::ControlSwitches {
+            mode, start, end, ..
+        } => match mode {
+            0 => uses.push((Switch(start), Write, "Control Switches")),
+            1 => uses.extend((start..=end).map(|id| (Switch(id), Write, "Control Switches"))),
+            _ => uses.push((Variable(start), Read, "Control Switches (switch id)")),
+        },
+        Instruction::ControlVariables {
+            mode,
+            start,
+            end,
+            operand,
+            value1,
+            ..
+        } => {
+            match mode {
+                0 => uses.push((Variable(start), Write, "Control Variables")),
+                1 => {
+                    uses.extend((start..=end).map(|id| (Variable(id), Write, "Control Variables")))
+                }
+                _ => uses.push((Variable(start), Read, "Control Variables (variable id)")),
+            }
+            match operand {
+                1 => uses.push((Variable(value1), Read, "Control Variables (operand)")),
+                2 => uses.push((Variable(value1), Read, "Control Variables (operand id)")),
+                _ => (),
+            }
+        }
+        Instruction::ControlTimer {
+            operand: 1,
+            seconds,
+            ..
+        } => uses.push((Variable(seconds), Read, "Control Timer")),
+        Instruction::ChangeGold {
+            operand: 1, value, ..
+        } => uses.push((Variable(value), Read, "Change Gold")),
+        Instruction::ChangeItems {
+            item_target,
+            item,
+            operand,
+            value,
+            ..
+        } => {
+            if item_target == 1 {
+                uses.push((Variable(item), Read, "Change Items (item)"));
+            }
+            if operand == 1 {
+                uses.push((Variable(value), Read, "Change Items (amount)"));
+            }
+        }
+        Instruction::ChangePartyMembers {
+            operand: 1, actor, ..
+        } => uses.push((Variable(actor), Read, "Change Party Members")),
+        Instruction::ChangeLevel {
+            actor_operand,
+            actor,
+            operand,
+            value,
+            ..
+        }
+        | Instruction::ChangeParameters {
+            actor_operand,
+            actor,
+            operand,
+            value,
+            ..
+        }
+        | Instruction::ChangeSkills {
+            actor_operand,
+            actor,
+            operand,
+            value,
+            ..
+        } => {
+            if actor_operand == 2 {
+                uses.push((Variable(actor), Read, "Actor"));
+            }
+            if operand == 1 {
+                uses.push((Variable(value), Read, "Value"));
+            }
+        }
+        Instruction::ChangeEquipment {
+            actor_operand: 2,
+            actor,
+            ..
+        } => uses.push((Variable(actor), Read, "Change Equipment (actor)")),
+        Instruction::RecoverAll { operand: 2, value } => {
+            uses.push((Variable(value), Read, "Recover All"));
+        }
+        Instruction::GetPlayerLocation {
+            output_map,
+            output_x,
+            output_y,
+        } => {
+            uses.push((Variable(output_map), Write, "Get Player Location (map)"));
+            uses.push((Variable(output_x), Write, "Get Player Location (x)"));
+            uses.push((Variable(output_y), Write, "Get Player Location (y)"));
+        }
+        Instruction::MoveToVariableLocation {
+            map_variable,
+            x_variable,
+            y_variable,
+        } => {
+            uses.push((Variable(map_variable), Read, "Teleport (map)"));
+            uses.push((Variable(x_variable), Read, "Teleport (x)"));
+            uses.push((Variable(y_variable), Read, "Teleport (y)"));
+        }
+        Instruction::SetVehicleLocation {
+            operand: 1,
+            map,
+            x,
+            y,
+            ..
+        } => {
+            uses.push((Variable(map), Read, "Set Vehicle Location (map)"));
+            uses.push((Variable(x), Read, "Set Vehicle Location (x)"));
+            uses.push((Variable(y), Read, "Set Vehicle Location (y)"));
+        }
+        Instruction::SetEventLocation {
+            mode: 1,
+            x_pos,
+            y_pos,
+            ..
+        } => {
+            uses.push((Variable(x_pos), Read, "Set Event Location (x)"));
+            uses.push((Variable(y_pos), Read, "Set Event Location (y)"));
+        }
+        Instruction::StoreTerrainID {
+            operand,
+            x,
+            y,
+            output,
+        }
+        | Instruction::GetEventLocation {
+            mode: operand,
+            x,
+            y,
+            output,
+        } => {
+            if operand == 1 {
+                uses.push((Variable(x), Read, "Position (x)"));
+                uses.push((Variable(y), Read, "Position (y)"));
+            }
+            uses.push((Variable(output), Write, "Output"));
+        }
+        Instruction::KeyInputProcessing { ref args } => {
+            if let Some(&variable) = args.first() {
+                uses.push((Variable(variable), Write, "Key Input Processing"));
+            }
+        }
+        Instruction::SetTeleportPoint {
+            switch_enabled: 1,
+            switch,
+            ..
+        }
+        | Instruction::SetEscapeLocation {
+            switch_enabled: 1,
+            switch,
+            ..
+        } => uses.push((Switch(switch), Write, "Teleport Switch")),
+        Instruction::ConditionalBranch {
+            mode,
+            field1,
+            field2,
+            field3,
+            ..
+        } => match mode {
+            0 => uses.push((Switch(field1), Read, "Conditional Branch")),
+            1 => {
+                uses.push((Variable(field1), Read, "Conditional Branch"));
+                if field2 == 1 {
+                    uses.push((Variable(field3), Read, "Conditional Branch (operand)"));
+                }
+            }
+            _ => (),
+        },
+        Instruction::CallEvent {
+            mode: 2,
+            index,
+            page,
+        } => {
+            uses.push((Variable(index), Read, "Call Event (event)"));
+            uses.push((Variable(page), Read, "Call Event (page)"));
+        }
+        Instruction::PlayMovie {
+            operand: 1, x, y, ..
+        } => {
+            uses.push((Variable(x), Read, "Play Movie (x)"));
+            uses.push((Variable(y), Read, "Play Movie (y)"));
+        }
+        _ => (),
+    }
+    uses
+}
+
+/// Draws the references to `target`, returning a location if one was clicked.
+pub fn show(
+    ui: &mut egui::Ui,
+    project: Option<&Project>,
+    target: Target,
+    encoding: crate::code_page::CodePage,
+) -> Option<Location> {
+    let Some(project) = project else {
+        ui.label("Open a project to search for references.");
+        return None;
+    };
+
+    let (prefix, id, names) = match target {
+        Target::Switch(id) => ("S", id, project.switch_names()),
+        Target::Variable(id) => ("V", id, project.variable_names()),
+    };
+    let name = names
+        .into_iter()
+        .find_map(|(index, name)| (index == id).then_some(name));
+    ui.heading(crate::labels::named(prefix, id, name, encoding));
+
+    let references = project.references.get(target);
+    if references.is_empty() {
+        ui.label("No references.");
+        return None;
+    }
+
+    let mut clicked = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("references").striped(true).show(ui, |ui| {
+            for reference in references {
+                ui.label(match reference.access {
+                    Access::Read => "Read",
+                    Access::Write => "Write",
+                });
+                ui.label(reference.usage);
+                if ui
+                    .link(project.describe(reference.location, encoding))
+                    .clicked()
+                {
+                    clicked = Some(reference.location);
+                }
+                ui.end_row();
+            }
+        });
+    });
+    clicked
+}
@@ -0,0 +1,444 @@
+//! Software rendering of map units, shared by the map view and image export.
+
+use std::ops::Range;
+
+use image::{Rgba, RgbaImage};
+use lcf::lmu::LcfMapUnit;
+
+use crate::{
+    assets::Assets,
+    chipset::{COLUMNS, ChipSet, LOWER_TILES, Passability, UPPER_TILES},
+    sheet::{FRAME_HEIGHT, FRAME_WIDTH, TILE},
+};
+
+/// Which animation frame water and animated tiles are drawn with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Frame {
+    /// Column of the water autotiles, from 0 to 2.
+    pub water: u32,
+    /// Row of the animated tiles, from 0 to 3.
+    pub animated: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Layers {
+    pub lower: bool,
+    pub upper: bool,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Self {
+            lower: true,
+            upper: true,
+        }
+    }
+}
+
+/// Draws the tile layers of a map with the chipset it uses.
+pub fn render(
+    map_unit: &LcfMapUnit,
+    chipset: &RgbaImage,
+    layers: Layers,
+    frame: Frame,
+) -> RgbaImage {
+    let (columns, rows) = (0..map_unit.width, 0..map_unit.height);
+    render_area(map_unit, chipset, layers, Some(frame), columns, rows)
+}
+
+/// Draws the tile layers of the cells in `columns` and `rows`, the first of them at the
+/// top-left corner. Without a `frame`, lower layer tiles that animate are left out, so they can
+/// be drawn underneath.
+pub fn render_area(
+    map_unit: &LcfMapUnit,
+    chipset: &RgbaImage,
+    layers: Layers,
+    frame: Option<Frame>,
+    columns: Range<u32>,
+    rows: Range<u32>,
+) -> RgbaImage {
+    let mut target = RgbaImage::new(columns.len() as u32 * TILE, rows.len() as u32 * TILE);
+    for (enabled, layer) in [
+        (layers.lower, &map_unit.lower),
+        (layers.upper, &map_unit.upper),
+    ] {
+        if !enabled {
+            continue;
+        }
+        for row in rows.clone() {
+            for column in columns.clone() {
+                let Some(&tile) = layer.get((row * map_unit.width + column) as usize) else {
+                    continue;
+                };
+                if frame.is_none() && animates(tile) {
+                    continue;
+                }
+                let x = (column - columns.start) * TILE;
+                let y = (row - rows.start) * TILE;
+                draw_tile(&mut target, chipset, tile, x, y, frame.unwrap_or_default());
+            }
+        }
+    }
+    target
+}
+
+/// Whether a tile changes with the animation frame: water and the animated lower tiles.
+pub fn animates(tile: u16) -> bool {
+    tile < 3150
+}
+
+/// Draws every lower or upper layer tile of a chipset in rows, in the editor's palette order.
+pub fn palette(chipset: &RgbaImage, upper: bool, frame: Frame) -> RgbaImage {
+    let count = if upper { UPPER_TILES } else { LOWER_TILES };
+    let rows = count.div_ceil(COLUMNS) as u32;
+    let mut target = RgbaImage::new(COLUMNS as u32 * TILE, rows * TILE);
+    for index in 0..count {
+        let tile = if upper {
+            10000 + index as u16
+        } else {
+            crate::chipset::lower_tile(index)
+        };
+        let x = (index % COLUMNS) as u32 * TILE;
+        let y = (index / COLUMNS) as u32 * TILE;
+        draw_tile(&mut target, chipset, tile, x, y, frame);
+    }
+    target
+}
+
+/// Draws a single tile of either layer with its top-left corner at `x`, `y`.
+pub fn draw_tile(
+    target: &mut RgbaImage,
+    chipset: &RgbaImage,
+    tile: u16,
+    x: u32,
+    y: u32,
+    frame: Frame,
+) {
+    let tile = tile as u32;
+    match tile {
+        0..3000 => draw_water(target, chipset, tile, x, y, frame),
+        3000..3150 => {
+            let index = (tile - 3000) / 50;
+            let source = (48 + index * TILE, 64 + frame.animated * TILE);
+            blit(target, chipset, source, (TILE, TILE), (x, y));
+        }
+        4000..4600 => {
+            let block = (tile - 4000) / 50;
+            let variant = ((tile - 4000) % 50).min(47) as usize;
+            let (bx, by) = if block < 4 {
+                (block % 2 * 48, 128 + block / 2 * 64)
+            } else {
+                (96 + (block - 4) % 2 * 48, (block - 4) / 2 * 64)
+            };
+            for (quarter, &subtile) in AUTOTILE[variant].iter().enumerate() {
+                let source = (bx + (subtile - 1) % 6 * 8, by + (subtile - 1) / 6 * 8);
+                let offset = (x + quarter as u32 % 2 * 8, y + quarter as u32 / 2 * 8);
+                blit(target, chipset, source, (8, 8), offset);
+            }
+        }
+        5000..5144 => {
+            let source = crate::sheet::lower_tile(tile - 5000);
+            blit(target, chipset, source, (TILE, TILE), (x, y));
+        }
+        10000..10144 => {
+            let source = crate::sheet::upper_tile(tile - 10000);
+            blit(target, chipset, source, (TILE, TILE), (x, y));
+        }
+        _ => (),
+    }
+}
+
+/// Water is made of 8×8 subtiles: shore edges from block A, and open water from block B.
+fn draw_water(
+    target: &mut RgbaImage,
+    chipset: &RgbaImage,
+    tile: u32,
+    x: u32,
+    y: u32,
+    frame: Frame,
+) {
+    let block = tile / 1000;
+    let b_subtile = (tile % 1000) / 50;
+    let a_subtile = ((tile % 50) as usize).min(46);
+    let frame_x = frame.water * TILE;
+
+    for j in 0..2 {
+        for i in 0..2 {
+            let source = match WATER[a_subtile][j as usize][i as usize] {
+                Some(edge) => {
+                    let base = if block == 1 { 48 } else { 0 };
+                    (base + frame_x + i * 8, edge * TILE + j * 8)
+                }
+                None => {
+                    let mut kind = (b_subtile >> (j * 2 + i)) & 1;
+                    if block == 2 {
+                        kind += 2;
+                    }
+                    (frame_x + i * 8, 64 + kind * TILE + j * 8)
+                }
+            };
+            blit(target, chipset, source, (8, 8), (x + i * 8, y + j * 8));
+        }
+    }
+}
+
+/// Copies the opaque pixels of a rectangle of `source` onto `target`, clipping at both edges.
+pub fn blit(
+    target: &mut RgbaImage,
+    source: &RgbaImage,
+    (sx, sy): (u32, u32),
+    (width, height): (u32, u32),
+    (dx, dy): (u32, u32),
+) {
+    for y in 0..height {
+        for x in 0..width {
+            let (Some(pixel), true) = (
+                source.get_pixel_checked(sx + x, sy + y),
+                dx + x < target.width() && dy + y < target.height(),
+            ) else {
+                continue;
+            };
+            if pixel.0[3] != 0 {
+                target.put_pixel(dx + x, dy + y, *pixel);
+            }
+        }
+    }
+}
+
+/// Draws the first page graphic of every event, or a yellow frame for events without one.
+pub fn draw_events(
+    target: &mut RgbaImage,
+    map_unit: &LcfMapUnit,
+    chipset: &RgbaImage,
+    assets: &mut Assets,
+    encoding: crate::code_page::CodePage,
+) {
+    for event in &map_unit.events {
+        let (x, y) = (event.x * TILE, event.y * TILE);
+        let graphic = event.pages.first().map(|page| &page.graphic);
+        if let Some(graphic) = graphic
+            && graphic.file.is_empty()
+            && graphic.index != 0
+        {
+            let source = crate::sheet::upper_tile(graphic.index);
+            blit(target, chipset, source, (TILE, TILE), (x, y));
+        } else if let Some(graphic) = graphic
+            && !graphic.file.is_empty()
+            && let Some(charset) = assets.image("CharSet", &graphic.file, encoding)
+        {
+            let (sx, sy) = crate::sheet::charset_frame(
+                graphic.index,
+                graphic.direction as u32,
+                graphic.pattern,
+            );
+            // Characters are centred on their tile and stand on its bottom edge, so whatever
+            // sticks out past the top or left of the map is cut off.
+            let left = x as i64 + (TILE as i64 - FRAME_WIDTH as i64) / 2;
+            let top = y as i64 + TILE as i64 - FRAME_HEIGHT as i64;
+            let (clip_x, clip_y) = ((-left).max(0) as u32, (-top).max(0) as u32);
+            blit(
+                target,
+                &charset,
+                (sx + clip_x, sy + clip_y),
+                (FRAME_WIDTH - clip_x, FRAME_HEIGHT - clip_y),
+                (left.max(0) as u32, top.max(0) as u32),
+            );
+        } else {
+            let color = Rgba([255, 200, 0, 255]);
+            for i in 1..TILE - 1 {
+                for (dx, dy) in [(i, 1), (i, TILE - 2), (1, i), (TILE - 2, i)] {
+                    put(target, x + dx, y + dy, color);
+                }
+            }
+        }
+    }
+}
+
+/// Darkens the edges between tiles.
+pub fn draw_grid(target: &mut RgbaImage) {
+    let (width, height) = target.dimensions();
+    for y in 0..height {
+        for x in 0..width {
+            if x % TILE == 0 || y % TILE == 0 {
+                blend(target, x, y, Rgba([0, 0, 0, 96]));
+            }
+        }
+    }
+}
+
+/// Marks the blocked sides of every map cell in red, and star tiles with a yellow corner.
+pub fn draw_passability(target: &mut RgbaImage, map_unit: &LcfMapUnit, tables: &ChipSet) {
+    let red = Rgba([255, 0, 0, 192]);
+    for (index, (&lower, &upper)) in map_unit.lower.iter().zip(&map_unit.upper).enumerate() {
+        let Some(passability) = tables.cell_passability(lower, upper) else {
+            continue;
+        };
+        let x = index as u32 % map_unit.width * TILE;
+        let y = index as u32 / map_unit.width * TILE;
+
+        if passability.0 & 0x0F == 0 {
+            for i in 3..TILE - 3 {
+                for offset in [0, 1] {
+                    blend(target, x + i + offset, y + i, red);
+                    blend(target, x + TILE - 1 - i - offset, y + i, red);
+                }
+            }
+        } else {
+            for i in 0..TILE {
+                for (flag, (dx, dy)) in [
+                    (Passability::DOWN, (i, TILE - 2)),
+                    (Passability::LEFT, (1, i)),
+                    (Passability::RIGHT, (TILE - 2, i)),
+                    (Passability::UP, (i, 1)),
+                ] {
+                    if !passability.contains(flag) {
+                        blend(target, x + dx, y + dy, red);
+                    }
+                }
+            }
+        }
+
+        if tables
+            .passability(upper)
+            .is_some_and(|upper| upper.contains(Passability::STAR))
+            && upper != 10000
+        {
+            for (dx, dy) in [(TILE - 4, 2), (TILE - 3, 2), (TILE - 4, 3), (TILE - 3, 3)] {
+                put(target, x + dx, y + dy, Rgba([255, 255, 0, 255]));
+            }
+        }
+    }
+}
+
+fn put(target: &mut RgbaImage, x: u32, y: u32, color: Rgba<u8>) {
+    if x < target.width() && y < target.height() {
+        target.put_pixel(x, y, color);
+    }
+}
+
+/// Mixes `color` into a pixel by its alpha, leaving the pixel opaque.
+fn blend(target: &mut RgbaImage, x: u32, y: u32, color: Rgba<u8>) {
+    let Some(pixel) = target.get_pixel_mut_checked(x, y) else {
+        return;
+    };
+    let alpha = color.0[3] as u32;
+    for channel in 0..3 {
+        pixel.0[channel] = ((pixel.0[channel] as u32 * (255 - alpha)
+            + color.0[channel] as u32 * alpha)
+            / 255) as u8;
+    }
+    pixel.0[3] = 255;
+}
+
+/// Which row of block A each quarter of a water tile comes from, or `None` for open water.
+///
+/// Rows are: 0 outer corner, 1 vertical edge, 2 horizontal edge, 3 inner corner.
+const WATER: [[[Option<u32>; 2]; 2]; 47] = {
+    const N: Option<u32> = None;
+    const O: Option<u32> = Some(0);
+    const V: Option<u32> = Some(1);
+    const H: Option<u32> = Some(2);
+    const I: Option<u32> = Some(3);
+    [
+        [[N, N], [N, N]],
+        [[I, N], [N, N]],
+        [[N, I], [N, N]],
+        [[I, I], [N, N]],
+        [[N, N], [N, I]],
+        [[I, N], [N, I]],
+        [[N, I], [N, I]],
+        [[I, I], [N, I]],
+        [[N, N], [I, N]],
+        [[I, N], [I, N]],
+        [[N, I], [I, N]],
+        [[I, I], [I, N]],
+        [[N, N], [I, I]],
+        [[I, N], [I, I]],
+        [[N, I], [I, I]],
+        [[I, I], [I, I]],
+        [[V, N], [V, N]],
+        [[V, I], [V, N]],
+        [[V, N], [V, I]],
+        [[V, I], [V, I]],
+        [[H, H], [N, N]],
+        [[H, H], [N, I]],
+        [[H, H], [I, N]],
+        [[H, H], [I, I]],
+        [[N, V], [N, V]],
+        [[N, V], [I, V]],
+        [[I, V], [N, V]],
+        [[I, V], [I, V]],
+        [[N, N], [H, H]],
+        [[I, N], [H, H]],
+        [[N, I], [H, H]],
+        [[I, I], [H, H]],
+        [[V, V], [V, V]],
+        [[H, H], [H, H]],
+        [[O, H], [V, N]],
+        [[O, H], [V, I]],
+        [[H, O], [N, V]],
+        [[H, O], [I, V]],
+        [[N, V], [H, O]],
+        [[I, V], [H, O]],
+        [[V, N], [O, H]],
+        [[V, I], [O, H]],
+        [[O, O], [V, V]],
+        [[O, H], [O, H]],
+        [[V, V], [O, O]],
+        [[H, O], [H, O]],
+        [[O, O], [O, O]],
+    ]
+};
+
+/// The 8×8 subtiles making up each autotile variant, numbered from 1 across the 6×8 grid
+/// of an autotile block, in the order top-left, top-right, bottom-left, bottom-right.
+const AUTOTILE: [[u32; 4]; 48] = [
+    [27, 28, 33, 34],
+    [5, 28, 33, 34],
+    [27, 6, 33, 34],
+    [5, 6, 33, 34],
+    [27, 28, 33, 12],
+    [5, 28, 33, 12],
+    [27, 6, 33, 12],
+    [5, 6, 33, 12],
+    [27, 28, 11, 34],
+    [5, 28, 11, 34],
+    [27, 6, 11, 34],
+    [5, 6, 11, 34],
+    [27, 28, 11, 12],
+    [5, 28, 11, 12],
+    [27, 6, 11, 12],
+    [5, 6, 11, 12],
+    [25, 26, 31, 32],
+    [25, 6, 31, 32],
+    [25, 26, 31, 12],
+    [25, 6, 31, 12],
+    [15, 16, 21, 22],
+    [15, 16, 21, 12],
+    [15, 16, 11, 22],
+    [15, 16, 11, 12],
+    [29, 30, 35, 36],
+    [29, 30, 11, 36],
+    [5, 30, 35, 36],
+    [5, 30, 11, 36],
+    [39, 40, 45, 46],
+    [5, 40, 45, 46],
+    [39, 6, 45, 46],
+    [5, 6, 45, 46],
+    [25, 30, 31, 36],
+    [15, 16, 45, 46],
+    [13, 14, 19, 20],
+    [13, 14, 19, 12],
+    [17, 18, 23, 24],
+    [17, 18, 11, 24],
+    [41, 42, 47, 48],
+    [5, 42, 47, 48],
+    [37, 38, 43, 44],
+    [37, 6, 43, 44],
+    [13, 18, 19, 24],
+    [13, 14, 43, 44],
+    [37, 42, 43, 48],
+    [17, 18, 47, 48],
+    [13, 18, 43, 48],
+    [1, 2, 7, 8],
+];
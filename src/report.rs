@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::{project::Project, references::Target};
+
+/// Switches and variables that are named but never used, or used but never named.
+pub struct Report {
+    pub unused: Vec<Target>,
+    pub undefined: Vec<(Target, Problem)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Problem {
+    Unnamed,
+    OutOfRange,
+}
+
+impl Report {
+    pub fn build(project: &Project) -> Self {
+        let mut report = Self {
+            unused: Vec::new(),
+            undefined: Vec::new(),
+        };
+
+        let switches = project.switch_names();
+        report.check(
+            &switches,
+            project.switch_count(),
+            &project.references.switches,
+            Target::Switch,
+        );
+        let variables = project.variable_names();
+        report.check(
+            &variables,
+            project.variable_count(),
+            &project.references.variables,
+            Target::Variable,
+        );
+
+        report
+    }
+
+    fn check<T>(
+        &mut self,
+        names: &[(u32, &[u8])],
+        count: u32,
+        references: &std::collections::BTreeMap<u32, Vec<T>>,
+        target: fn(u32) -> Target,
+    ) {
+        for &(id, name) in names {
+            if !name.is_empty() && !references.contains_key(&id) {
+                self.unused.push(target(id));
+            }
+        }
+
+        for &id in references.keys() {
+            match names.iter().find(|(index, _)| *index == id) {
+                _ if id == 0 || id > count => {
+                    self.undefined.push((target(id), Problem::OutOfRange))
+                }
+                None | Some((_, [])) => self.undefined.push((target(id), Problem::Unnamed)),
+                Some(_) => (),
+            }
+        }
+    }
+}
+
+/// Draws the report, returning a switch or variable if one was clicked.
+pub fn show(
+    ui: &mut egui::Ui,
+    project: &Project,
+    report: &Report,
+    encoding: crate::code_page::CodePage,
+) -> Option<Target> {
+    let switches: HashMap<_, _> = project.switch_names().into_iter().collect();
+    let variables: HashMap<_, _> = project.variable_names().into_iter().collect();
+    let label = |target: Target| {
+        let (prefix, id, names) = match target {
+            Target::Switch(id) => ("S", id, &switches),
+            Target::Variable(id) => ("V", id, &variables),
+        };
+        crate::labels::named(prefix, id, names.get(&id).copied(), encoding)
+    };
+
+    let mut clicked = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::CollapsingHeader::new(format!("Unused ({})", report.unused.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                for target in &report.unused {
+                    if ui.link(label(*target)).clicked() {
+                        clicked = Some(*target);
+                    }
+                }
+            });
+
+        egui::CollapsingHeader::new(format!("Undefined ({})", report.undefined.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("undefined").striped(true).show(ui, |ui| {
+                    for (target, problem) in &report.undefined {
+                        if ui.link(label(*target)).clicked() {
+                            clicked = Some(*target);
+                        }
+                        ui.label(match problem {
+                            Problem::Unnamed => "No name",
+                            Problem::OutOfRange => "Beyond the database count",
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+    });
+    clicked
+}
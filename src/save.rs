@@ -0,0 +1,256 @@
+//! Reading and changing the parts of a save lcf keeps as bytes.
+
+use std::{collections::BTreeMap, path::Path};
+
+use lcf::{
+    helpers::{Array, Array2D, Chunk, ToChunkID, UnknownChunk},
+    raw::lsd::{LcfSaveDataChunk, RawLcfSaveData, SaveSystemChunk},
+};
+
+pub fn read(path: &Path) -> Result<RawLcfSaveData, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path)?;
+    Ok(RawLcfSaveData::read(&mut std::io::Cursor::new(bytes))?)
+}
+
+/// Writes a save to `path`, keeping the previous file as `.lsd.bak`.
+pub fn write(save: &RawLcfSaveData, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    save.write(&mut cursor)?;
+    if path.exists() {
+        std::fs::copy(path, path.with_extension("lsd.bak"))?;
+    }
+    std::fs::write(path, cursor.into_inner())?;
+    Ok(())
+}
+
+pub fn switches(save: &RawLcfSaveData) -> Vec<bool> {
+    system(save)
+        .find_map(|chunk| match chunk {
+            SaveSystemChunk::Switches(numbers) => {
+                Some(numbers.iter().map(|number| number.0 != 0).collect())
+            }
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+pub fn variables(save: &RawLcfSaveData) -> Vec<i32> {
+    system(save)
+        .find_map(|chunk| match chunk {
+            SaveSystemChunk::Variables(numbers) => Some(numbers.clone()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+fn system(save: &RawLcfSaveData) -> impl Iterator<Item = &SaveSystemChunk> {
+    save.0
+        .inner_vec
+        .iter()
+        .filter_map(|chunk| match &chunk.data {
+            LcfSaveDataChunk::System(chunks) => Some(chunks),
+            _ => None,
+        })
+        .flat_map(|chunks| chunks.inner_vec.iter().map(|chunk| &chunk.data))
+}
+
+pub fn inventory(save: &RawLcfSaveData) -> Option<Array<Chunk<UnknownChunk>>> {
+    save.0.inner_vec.iter().find_map(|chunk| match &chunk.data {
+        LcfSaveDataChunk::Inventory { bytes } => crate::fields::chunks(bytes),
+        _ => None,
+    })
+}
+
+pub fn map_info(save: &RawLcfSaveData) -> Option<Array<Chunk<UnknownChunk>>> {
+    save.0.inner_vec.iter().find_map(|chunk| match &chunk.data {
+        LcfSaveDataChunk::MapInfo { bytes } => crate::fields::chunks(bytes),
+        _ => None,
+    })
+}
+
+pub fn party_location(save: &RawLcfSaveData) -> Option<Array<Chunk<UnknownChunk>>> {
+    save.0.inner_vec.iter().find_map(|chunk| match &chunk.data {
+        LcfSaveDataChunk::PartyLocation { bytes } => crate::fields::chunks(bytes),
+        _ => None,
+    })
+}
+
+/// The party and vehicles, whose locations a save keeps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Character {
+    Party,
+    Boat,
+    Ship,
+    Airship,
+}
+
+impl Character {
+    pub const fn to_str(self) -> &'static str {
+        match self {
+            Self::Party => "Party",
+            Self::Boat => "Boat",
+            Self::Ship => "Ship",
+            Self::Airship => "Airship",
+        }
+    }
+}
+
+/// Where a character stands, and the direction it moves in: 0 up, 1 right, 2 down, 3 left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub map: u32,
+    pub x: u32,
+    pub y: u32,
+    pub direction: u32,
+}
+
+/// The party and the vehicles placed on a map. Vehicles the game never placed are on map 0.
+pub fn positions(save: &RawLcfSaveData) -> Vec<(Character, Position)> {
+    save.0
+        .inner_vec
+        .iter()
+        .filter_map(|chunk| {
+            let (character, bytes) = match &chunk.data {
+                LcfSaveDataChunk::PartyLocation { bytes } => (Character::Party, bytes),
+                LcfSaveDataChunk::BoatLocation { bytes } => (Character::Boat, bytes),
+                LcfSaveDataChunk::ShipLocation { bytes } => (Character::Ship, bytes),
+                LcfSaveDataChunk::AirshipLocation { bytes } => (Character::Airship, bytes),
+                _ => return None,
+            };
+            let chunks = crate::fields::chunks(bytes)?;
+            let number = |id| {
+                crate::fields::get(&chunks, id)
+                    .and_then(crate::fields::number)
+                    .unwrap_or_default()
+            };
+            let position = Position {
+                map: number(11),
+                x: number(12),
+                y: number(13),
+                // Characters face down until they first move.
+                direction: crate::fields::get(&chunks, 21)
+                    .and_then(crate::fields::number)
+                    .unwrap_or(2),
+            };
+            (position.map != 0).then_some((character, position))
+        })
+        .collect()
+}
+
+/// Item ids and how many of each the party holds.
+pub fn items(inventory: &Option<Array<Chunk<UnknownChunk>>>) -> BTreeMap<u32, u8> {
+    let Some(chunks) = inventory else {
+        return BTreeMap::new();
+    };
+    let ids = crate::fields::get(chunks, 12).map_or_default(crate::fields::shorts);
+    let counts = crate::fields::get(chunks, 13).unwrap_or_default();
+    ids.into_iter()
+        .zip(counts)
+        .map(|(id, count)| (id as u32, *count))
+        .collect()
+}
+
+pub fn pictures(save: &RawLcfSaveData) -> BTreeMap<u32, Array<Chunk<UnknownChunk>>> {
+    save.0
+        .inner_vec
+        .iter()
+        .find_map(|chunk| match &chunk.data {
+            LcfSaveDataChunk::Pictures { bytes } => crate::fields::records(bytes),
+            _ => None,
+        })
+        .map_or_default(|pictures| {
+            pictures
+                .inner_vec
+                .into_iter()
+                .map(|(id, chunks)| (id.0, chunks))
+                .collect()
+        })
+}
+
+pub fn actors(save: &RawLcfSaveData) -> Option<Array2D<UnknownChunk>> {
+    save.0.inner_vec.iter().find_map(|chunk| match &chunk.data {
+        LcfSaveDataChunk::Actors { bytes } => crate::fields::records(bytes),
+        _ => None,
+    })
+}
+
+/// Replaces the switches of a save, along with their count.
+pub fn set_switches(save: &mut RawLcfSaveData, switches: &[bool]) {
+    let numbers = switches
+        .iter()
+        .map(|on| lcf::helpers::Number(*on as u32))
+        .collect();
+    set_system(
+        save,
+        SaveSystemChunk::SwitchesSize(lcf::helpers::Number(switches.len() as u32)),
+    );
+    set_system(save, SaveSystemChunk::Switches(numbers));
+}
+
+/// Replaces the variables of a save, along with their count.
+pub fn set_variables(save: &mut RawLcfSaveData, variables: &[i32]) {
+    set_system(
+        save,
+        SaveSystemChunk::VariablesSize(lcf::helpers::Number(variables.len() as u32)),
+    );
+    set_system(save, SaveSystemChunk::Variables(variables.to_vec()));
+}
+
+fn set_system(save: &mut RawLcfSaveData, data: SaveSystemChunk) {
+    let Some(chunks) = save
+        .0
+        .inner_vec
+        .iter_mut()
+        .find_map(|chunk| match &mut chunk.data {
+            LcfSaveDataChunk::System(chunks) => Some(chunks),
+            _ => None,
+        })
+    else {
+        return;
+    };
+    let id = data.id();
+    let chunk = Chunk::from(data);
+    match chunks
+        .inner_vec
+        .iter()
+        .position(|chunk| chunk.data.id() >= id)
+    {
+        Some(position) if chunks.inner_vec[position].data.id() == id => {
+            chunks.inner_vec[position] = chunk;
+        }
+        Some(position) => chunks.inner_vec.insert(position, chunk),
+        None => chunks.inner_vec.push(chunk),
+    }
+}
+
+pub fn set_inventory(save: &mut RawLcfSaveData, inventory: &Array<Chunk<UnknownChunk>>) {
+    let bytes = crate::fields::encode_chunks(inventory);
+    set_chunk(save, LcfSaveDataChunk::Inventory { bytes });
+}
+
+pub fn set_party_location(save: &mut RawLcfSaveData, location: &Array<Chunk<UnknownChunk>>) {
+    let bytes = crate::fields::encode_chunks(location);
+    set_chunk(save, LcfSaveDataChunk::PartyLocation { bytes });
+}
+
+pub fn set_actors(save: &mut RawLcfSaveData, actors: &Array2D<UnknownChunk>) {
+    let bytes = crate::fields::encode_records(actors);
+    set_chunk(save, LcfSaveDataChunk::Actors { bytes });
+}
+
+pub fn set_map_info(save: &mut RawLcfSaveData, info: &Array<Chunk<UnknownChunk>>) {
+    let bytes = crate::fields::encode_chunks(info);
+    set_chunk(save, LcfSaveDataChunk::MapInfo { bytes });
+}
+
+/// Replaces the chunk of a save with the same id as `data`, or adds it where the engine writes it.
+fn set_chunk(save: &mut RawLcfSaveData, data: LcfSaveDataChunk) {
+    let chunks = &mut save.0.inner_vec;
+    let id = data.id();
+    let chunk = Chunk::from(data);
+    match chunks.iter().position(|chunk| chunk.data.id() >= id) {
+        Some(position) if chunks[position].data.id() == id => chunks[position] = chunk,
+        Some(position) => chunks.insert(position, chunk),
+        None => chunks.push(chunk),
+    }
+}
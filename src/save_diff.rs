@@ -0,0 +1,366 @@
+//! What changed between two saves, like before and after a cutscene.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::PathBuf,
+};
+
+use lcf::{
+    helpers::{Array, Chunk, UnknownChunk},
+    raw::lsd::RawLcfSaveData,
+};
+
+use crate::{fields::save_data, labels::named, project::Project, save};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    Switch,
+    Variable,
+    Party,
+    Item,
+    Gold,
+    Position,
+    Picture,
+}
+
+impl Category {
+    pub const fn to_str(self) -> &'static str {
+        match self {
+            Self::Switch => "Switch",
+            Self::Variable => "Variable",
+            Self::Party => "Party",
+            Self::Item => "Item",
+            Self::Gold => "Gold",
+            Self::Position => "Position",
+            Self::Picture => "Picture",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Change {
+    pub category: Category,
+    pub name: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Everything that differs between two saves. Names come from the project's database when
+/// there is one.
+pub fn diff(
+    before: &RawLcfSaveData,
+    after: &RawLcfSaveData,
+    project: Option<&Project>,
+    encoding: crate::code_page::CodePage,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let mut push = |category, name: String, before: String, after: String| {
+        if before != after {
+            changes.push(Change {
+                category,
+                name,
+                before,
+                after,
+            });
+        }
+    };
+
+    // Saves store switches and variables from index 0, while their ids start at 1.
+    let switch_names: HashMap<_, _> = project
+        .map_or_default(|project| project.switch_names())
+        .into_iter()
+        .collect();
+    let (old, new) = (save::switches(before), save::switches(after));
+    for index in 0..old.len().max(new.len()) {
+        let id = index as u32 + 1;
+        push(
+            Category::Switch,
+            named("S", id, switch_names.get(&id).copied(), encoding),
+            old.get(index).copied().unwrap_or_default().to_string(),
+            new.get(index).copied().unwrap_or_default().to_string(),
+        );
+    }
+
+    let variable_names: HashMap<_, _> = project
+        .map_or_default(|project| project.variable_names())
+        .into_iter()
+        .collect();
+    let (old, new) = (save::variables(before), save::variables(after));
+    for index in 0..old.len().max(new.len()) {
+        let id = index as u32 + 1;
+        push(
+            Category::Variable,
+            named("V", id, variable_names.get(&id).copied(), encoding),
+            old.get(index).copied().unwrap_or_default().to_string(),
+            new.get(index).copied().unwrap_or_default().to_string(),
+        );
+    }
+
+    let (old, new) = (save::inventory(before), save::inventory(after));
+    let actor_names: HashMap<_, _> = project
+        .map_or_default(|project| project.actor_names())
+        .into_iter()
+        .collect();
+    let party = |inventory: &Option<Array<Chunk<UnknownChunk>>>| {
+        inventory
+            .as_ref()
+            .and_then(|chunks| crate::fields::get(chunks, 2))
+            .map_or_default(crate::fields::shorts)
+            .into_iter()
+            .map(|id| {
+                let id = id as u32;
+                named("A", id, actor_names.get(&id).map(Vec::as_slice), encoding)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    push(
+        Category::Party,
+        "Members".to_string(),
+        party(&old),
+        party(&new),
+    );
+
+    let item_names: HashMap<_, _> = project
+        .map_or_default(|project| project.item_names())
+        .into_iter()
+        .collect();
+    let (old_items, new_items) = (save::items(&old), save::items(&new));
+    for id in old_items
+        .keys()
+        .chain(new_items.keys())
+        .collect::<BTreeSet<_>>()
+    {
+        push(
+            Category::Item,
+            named("I", *id, item_names.get(id).map(Vec::as_slice), encoding),
+            old_items.get(id).copied().unwrap_or_default().to_string(),
+            new_items.get(id).copied().unwrap_or_default().to_string(),
+        );
+    }
+
+    let gold = |inventory: &Option<Array<Chunk<UnknownChunk>>>| {
+        inventory
+            .as_ref()
+            .and_then(|chunks| crate::fields::get(chunks, 21))
+            .and_then(crate::fields::number)
+            .unwrap_or_default()
+            .to_string()
+    };
+    push(Category::Gold, "Gold".to_string(), gold(&old), gold(&new));
+
+    let (old, new) = (save::party_location(before), save::party_location(after));
+    let map = |location: &Option<Array<Chunk<UnknownChunk>>>| {
+        let id = location
+            .as_ref()
+            .and_then(|chunks| crate::fields::get(chunks, 11))
+            .and_then(crate::fields::number)
+            .unwrap_or_default();
+        named(
+            "Map",
+            id,
+            project.and_then(|project| project.map_name(id)),
+            encoding,
+        )
+    };
+    push(Category::Position, "Map".to_string(), map(&old), map(&new));
+    for (id, name) in [(12, "X"), (13, "Y"), (21, "Direction")] {
+        let value = |location: &Option<Array<Chunk<UnknownChunk>>>| {
+            location
+                .as_ref()
+                .and_then(|chunks| crate::fields::get(chunks, id))
+                .and_then(crate::fields::number)
+                .unwrap_or_default()
+                .to_string()
+        };
+        push(
+            Category::Position,
+            name.to_string(),
+            value(&old),
+            value(&new),
+        );
+    }
+
+    let (old, new) = (save::pictures(before), save::pictures(after));
+    for id in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+        for field in save_data::PICTURE {
+            let value = |pictures: &BTreeMap<u32, Array<Chunk<UnknownChunk>>>| {
+                let bytes = pictures
+                    .get(id)
+                    .and_then(|chunks| crate::fields::get(chunks, field.id));
+                match bytes {
+                    Some(bytes) => crate::fields::value(field.kind, bytes, encoding)
+                        .unwrap_or_else(|| format!("{bytes:?}")),
+                    None => "-".to_string(),
+                }
+            };
+            push(
+                Category::Picture,
+                format!("Picture {id} {}", field.name),
+                value(&old),
+                value(&new),
+            );
+        }
+    }
+
+    changes
+}
+
+/// The two saves picked in the save diff window, and what changed between them.
+#[derive(Default)]
+pub struct View {
+    before: Option<PathBuf>,
+    after: Option<PathBuf>,
+    changes: Option<Result<Vec<Change>, String>>,
+    category: Option<Category>,
+}
+
+pub fn show(
+    ui: &mut egui::Ui,
+    view: &mut View,
+    project: Option<&Project>,
+    encoding: crate::code_page::CodePage,
+) {
+    ui.horizontal(|ui| {
+        for (label, path) in [("Before", &mut view.before), ("After", &mut view.after)] {
+            if ui.button(format!("{label}…")).clicked()
+                && let Some(picked) = rfd::FileDialog::new()
+                    .add_filter("Save file", &["lsd"])
+                    .pick_file()
+            {
+                *path = Some(picked);
+                view.changes = None;
+            }
+            ui.label(path.as_ref().map_or_else(
+                || "None".to_string(),
+                |path| {
+                    path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned()
+                },
+            ));
+            ui.separator();
+        }
+        egui::ComboBox::from_id_salt("save diff category")
+            .selected_text(view.category.map_or("All", Category::to_str))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut view.category, None, "All");
+                for category in [
+                    Category::Switch,
+                    Category::Variable,
+                    Category::Party,
+                    Category::Item,
+                    Category::Gold,
+                    Category::Position,
+                    Category::Picture,
+                ] {
+                    ui.selectable_value(&mut view.category, Some(category), category.to_str());
+                }
+            });
+        if ui.button("Refresh").clicked() {
+            view.changes = None;
+        }
+    });
+
+    let (Some(before), Some(after)) = (&view.before, &view.after) else {
+        ui.label("Pick two save files to compare.");
+        return;
+    };
+    let changes = view.changes.get_or_insert_with(|| {
+        let before = save::read(before).map_err(|err| format!("{}: {err}", before.display()))?;
+        let after = save::read(after).map_err(|err| format!("{}: {err}", after.display()))?;
+        Ok(diff(&before, &after, project, encoding))
+    });
+    let changes = match changes {
+        Ok(changes) => changes,
+        Err(err) => {
+            ui.label(format!("Failed to read save: {err}"));
+            return;
+        }
+    };
+    if changes.is_empty() {
+        ui.label("The saves are the same.");
+        return;
+    }
+
+    egui::ScrollArea::both().show(ui, |ui| {
+        egui::Grid::new("save diff").striped(true).show(ui, |ui| {
+            for heading in ["Category", "Name", "Before", "After"] {
+                ui.strong(heading);
+            }
+            ui.end_row();
+            for change in changes.iter().filter(|change| {
+                view.category
+                    .is_none_or(|category| category == change.category)
+            }) {
+                ui.label(change.category.to_str());
+                ui.label(&change.name);
+                ui.label(&change.before);
+                ui.label(&change.after);
+                ui.end_row();
+            }
+        });
+    });
+}
+
+const USAGE: &str = "\
+Usage: lcf-explorer diff <before.lsd> <after.lsd> [options]
+
+Options:
+    --project <folder>   Name switches, variables, actors and items from this project.
+    --encoding <name>    Text encoding of the project, like windows-1252 or Shift_JIS.";
+
+/// Runs `lcf-explorer diff` with the arguments after it, returning the exit code.
+pub fn cli(args: &[String]) -> i32 {
+    let mut root = None;
+    let mut saves = Vec::new();
+
+    let (encoding, args) = match crate::cli::common(args, USAGE) {
+        Ok(common) => common,
+        Err(code) => return code,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--project" => match args.next() {
+                Some(folder) => root = Some(PathBuf::from(folder)),
+                None => return crate::cli::usage(USAGE, "--project needs a folder"),
+            },
+            _ if arg.starts_with("--") => {
+                return crate::cli::usage(USAGE, &format!("unknown option {arg}"));
+            }
+            _ => saves.push(PathBuf::from(arg)),
+        }
+    }
+    let [before, after] = saves.as_slice() else {
+        return crate::cli::usage(USAGE, "expected two save files");
+    };
+
+    let project = match root.map(|root| Project::open(&root)).transpose() {
+        Ok(project) => project,
+        Err(err) => {
+            eprintln!("Failed to open project: {err}");
+            return 1;
+        }
+    };
+    let saves = save::read(before).and_then(|before| Ok((before, save::read(after)?)));
+    let (before, after) = match saves {
+        Ok(saves) => saves,
+        Err(err) => {
+            eprintln!("Failed to read save: {err}");
+            return 1;
+        }
+    };
+
+    for change in diff(&before, &after, project.as_ref(), encoding) {
+        println!(
+            "{:<9} {}: {} -> {}",
+            change.category.to_str(),
+            change.name,
+            change.before,
+            change.after
+        );
+    }
+    0
+}
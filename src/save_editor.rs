@@ -0,0 +1,383 @@
+//! A form for the parts of a save QA changes to reach a game state: switches, variables, where
+//! the party stands, what it carries and how its actors are doing.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use lcf::{
+    helpers::{Array, Chunk, UnknownChunk},
+    raw::lsd::RawLcfSaveData,
+};
+
+use crate::{labels::named, project::Project, save};
+
+struct Actor {
+    id: u32,
+    name: Vec<u8>,
+    level: u32,
+    hp: u32,
+    sp: u32,
+    /// Added to the max HP and SP of the database's curves, by items and events.
+    hp_bonus: i32,
+    sp_bonus: i32,
+}
+
+impl Actor {
+    /// Max HP and SP at the actor's level, when the project has the actor's curves.
+    fn max(&self, project: Option<&Project>) -> Option<(u32, u32)> {
+        let parameters = project?.actor_parameters(self.id)?;
+        let levels = parameters.len() / 6;
+        let level = (self.level as usize).clamp(1, levels.max(1)) - 1;
+        let hp = *parameters.get(level)? as i32 + self.hp_bonus;
+        let sp = *parameters.get(levels + level)? as i32 + self.sp_bonus;
+        Some((hp.max(0) as u32, sp.max(0) as u32))
+    }
+}
+
+/// The values being edited, read from the save of an open tab.
+pub struct Editor {
+    pub path: PathBuf,
+    switches: Vec<bool>,
+    variables: Vec<i32>,
+    map: u32,
+    x: u32,
+    y: u32,
+    gold: u32,
+    /// Item ids and counts, in the order the inventory lists them.
+    items: Vec<(u16, u8)>,
+    actors: Vec<Actor>,
+    filter: String,
+    new_item: u32,
+    /// Why the last save or revert failed.
+    error: Option<String>,
+}
+
+impl Editor {
+    pub fn load(path: PathBuf, save: &RawLcfSaveData) -> Self {
+        let number = |chunks: Option<&Array<Chunk<UnknownChunk>>>, id| {
+            chunks
+                .and_then(|chunks| crate::fields::get(chunks, id))
+                .and_then(crate::fields::number)
+                .unwrap_or_default()
+        };
+
+        let location = save::party_location(save);
+        let inventory = save::inventory(save);
+        let items = inventory.as_ref().map_or_default(|chunks| {
+            let ids = crate::fields::get(chunks, 12).map_or_default(crate::fields::shorts);
+            let counts = crate::fields::get(chunks, 13).unwrap_or_default();
+            ids.into_iter().zip(counts.iter().copied()).collect()
+        });
+        let actors = save::actors(save).map_or_default(|actors| {
+            actors
+                .inner_vec
+                .iter()
+                .map(|(id, chunks)| Actor {
+                    id: id.0,
+                    name: crate::fields::get(chunks, 1).unwrap_or_default().to_vec(),
+                    level: number(Some(chunks), 31).max(1),
+                    hp: number(Some(chunks), 71),
+                    sp: number(Some(chunks), 72),
+                    hp_bonus: number(Some(chunks), 33) as i32,
+                    sp_bonus: number(Some(chunks), 34) as i32,
+                })
+                .collect()
+        });
+
+        Self {
+            path,
+            switches: save::switches(save),
+            variables: save::variables(save),
+            map: number(location.as_ref(), 11),
+            x: number(location.as_ref(), 12),
+            y: number(location.as_ref(), 13),
+            gold: number(inventory.as_ref(), 21),
+            items,
+            actors,
+            filter: String::new(),
+            new_item: 1,
+            error: None,
+        }
+    }
+
+    /// Writes the edited values into `save`, keeping every chunk the editor doesn't cover and
+    /// adding the party location and inventory if the save has none.
+    pub fn apply(&self, save: &mut RawLcfSaveData) {
+        save::set_switches(save, &self.switches);
+        save::set_variables(save, &self.variables);
+
+        let empty = || Array {
+            inner_vec: Vec::new(),
+            null_terminated: true,
+        };
+        let mut location = save::party_location(save).unwrap_or_else(empty);
+        let map = crate::fields::get(&location, 11).and_then(crate::fields::number);
+        for (id, value) in [(11, self.map), (12, self.x), (13, self.y)] {
+            crate::fields::set(&mut location, id, crate::fields::encode_number(value));
+        }
+        save::set_party_location(save, &location);
+
+        // The state of the events, the chipset and the tile substitutions are those of the map
+        // the game was saved on, so the new map starts from its own.
+        if map != Some(self.map)
+            && let Some(mut info) = save::map_info(save)
+        {
+            for id in [5, 11, 21, 22] {
+                crate::fields::remove(&mut info, id);
+            }
+            save::set_map_info(save, &info);
+        }
+
+        let mut inventory = save::inventory(save).unwrap_or_else(empty);
+        // Uses left on each item, kept for items that stay in the inventory.
+        let uses: HashMap<u16, u8> = crate::fields::get(&inventory, 12)
+            .map_or_default(crate::fields::shorts)
+            .into_iter()
+            .zip(
+                crate::fields::get(&inventory, 14)
+                    .unwrap_or_default()
+                    .iter()
+                    .copied(),
+            )
+            .collect();
+        let ids = self
+            .items
+            .iter()
+            .flat_map(|(id, _)| id.to_le_bytes())
+            .collect();
+        let counts = self.items.iter().map(|(_, count)| *count).collect();
+        let item_uses = self
+            .items
+            .iter()
+            .map(|(id, _)| uses.get(id).copied().unwrap_or_default())
+            .collect();
+        let size = crate::fields::encode_number(self.items.len() as u32);
+        crate::fields::set(&mut inventory, 11, size);
+        crate::fields::set(&mut inventory, 12, ids);
+        crate::fields::set(&mut inventory, 13, counts);
+        crate::fields::set(&mut inventory, 14, item_uses);
+        crate::fields::set(&mut inventory, 21, crate::fields::encode_number(self.gold));
+        save::set_inventory(save, &inventory);
+
+        // Actors only come from the save, so without them there is nothing to write.
+        if let Some(mut actors) = save::actors(save) {
+            for (id, chunks) in &mut actors.inner_vec {
+                let Some(actor) = self.actors.iter().find(|actor| actor.id == id.0) else {
+                    continue;
+                };
+                for (id, value) in [(31, actor.level), (71, actor.hp), (72, actor.sp)] {
+                    crate::fields::set(chunks, id, crate::fields::encode_number(value));
+                }
+            }
+            save::set_actors(save, &actors);
+        }
+    }
+}
+
+/// Draws the editor, returning whether `save` was written, or read again from disk.
+pub fn show(
+    ui: &mut egui::Ui,
+    editor: &mut Editor,
+    save: &mut RawLcfSaveData,
+    project: Option<&Project>,
+    encoding: crate::code_page::CodePage,
+) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        if ui.button("Save").clicked() {
+            // Edits a copy, so a save that fails to write leaves the tab as it is on disk.
+            let mut edited = save.clone();
+            editor.apply(&mut edited);
+            match save::write(&edited, &editor.path) {
+                Ok(()) => {
+                    *save = edited;
+                    editor.error = None;
+                    changed = true;
+                }
+                Err(err) => {
+                    editor.error = Some(format!("Failed to save {}: {err}", editor.path.display()))
+                }
+            }
+        }
+        if ui.button("Revert").clicked() {
+            match save::read(&editor.path) {
+                Ok(read) => {
+                    *save = read;
+                    *editor = Editor::load(editor.path.clone(), save);
+                    changed = true;
+                }
+                Err(err) => {
+                    editor.error = Some(format!("Failed to read {}: {err}", editor.path.display()))
+                }
+            }
+        }
+        ui.separator();
+        ui.label("Filter");
+        ui.text_edit_singleline(&mut editor.filter);
+    });
+    if let Some(error) = &editor.error {
+        ui.colored_label(ui.visuals().error_fg_color, error);
+    }
+    let filter = editor.filter.to_lowercase();
+    let matches = |label: &str| filter.is_empty() || label.to_lowercase().contains(&filter);
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::CollapsingHeader::new("Party Location")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Map");
+                    match project {
+                        Some(project) => {
+                            let label = |id| named("Map", id, project.map_name(id), encoding);
+                            egui::ComboBox::from_id_salt("save editor map")
+                                .selected_text(label(editor.map))
+                                .show_ui(ui, |ui| {
+                                    for map in &project.maps {
+                                        ui.selectable_value(&mut editor.map, map.id, label(map.id));
+                                    }
+                                });
+                        }
+                        None => {
+                            ui.add(egui::DragValue::new(&mut editor.map).range(1..=9999));
+                        }
+                    }
+                    let size = project
+                        .and_then(|project| project.map(editor.map))
+                        .map(|map| (map.unit.width, map.unit.height));
+                    let (width, height) = size.unwrap_or((u32::MAX, u32::MAX));
+                    ui.label("X");
+                    ui.add(egui::DragValue::new(&mut editor.x).range(0..=width.saturating_sub(1)));
+                    ui.label("Y");
+                    ui.add(egui::DragValue::new(&mut editor.y).range(0..=height.saturating_sub(1)));
+                });
+            });
+
+        egui::CollapsingHeader::new("Inventory")
+            .default_open(true)
+            .show(ui, |ui| {
+                let item_names: HashMap<_, _> = project
+                    .map_or_default(|project| project.item_names())
+                    .into_iter()
+                    .collect();
+                let item =
+                    |id: u32| named("I", id, item_names.get(&id).map(Vec::as_slice), encoding);
+
+                ui.horizontal(|ui| {
+                    ui.label("Gold");
+                    ui.add(egui::DragValue::new(&mut editor.gold).range(0..=999_999));
+                });
+                let mut removed = None;
+                egui::Grid::new("save editor items")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (index, (id, count)) in editor.items.iter_mut().enumerate() {
+                            ui.label(item(*id as u32));
+                            ui.add(egui::DragValue::new(count).range(1..=99));
+                            if ui.small_button("✖").clicked() {
+                                removed = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(index) = removed {
+                    editor.items.remove(index);
+                }
+                ui.horizontal(|ui| {
+                    if item_names.is_empty() {
+                        ui.add(egui::DragValue::new(&mut editor.new_item).range(1..=9999));
+                    } else {
+                        let mut ids = item_names.keys().copied().collect::<Vec<_>>();
+                        ids.sort_unstable();
+                        egui::ComboBox::from_id_salt("save editor new item")
+                            .selected_text(item(editor.new_item))
+                            .show_ui(ui, |ui| {
+                                for id in ids {
+                                    ui.selectable_value(&mut editor.new_item, id, item(id));
+                                }
+                            });
+                    }
+                    if ui.button("Add").clicked() {
+                        let id = editor.new_item as u16;
+                        match editor.items.iter_mut().find(|(item, _)| *item == id) {
+                            Some((_, count)) => *count = count.saturating_add(1).min(99),
+                            None => editor.items.push((id, 1)),
+                        }
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new("Actors")
+            .default_open(true)
+            .show(ui, |ui| {
+                let actor_names: HashMap<_, _> = project
+                    .map_or_default(|project| project.actor_names())
+                    .into_iter()
+                    .collect();
+                egui::Grid::new("save editor actors")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for heading in ["Actor", "Level", "HP", "SP", ""] {
+                            ui.strong(heading);
+                        }
+                        ui.end_row();
+                        for actor in &mut editor.actors {
+                            // Actors renamed in game keep their new name in the save.
+                            let name = if actor.name.is_empty() {
+                                actor_names.get(&actor.id).map(Vec::as_slice)
+                            } else {
+                                Some(actor.name.as_slice())
+                            };
+                            ui.label(named("A", actor.id, name, encoding));
+                            ui.add(egui::DragValue::new(&mut actor.level).range(1..=99));
+                            ui.add(egui::DragValue::new(&mut actor.hp).range(0..=9999));
+                            ui.add(egui::DragValue::new(&mut actor.sp).range(0..=9999));
+                            let max = actor.max(project);
+                            if ui
+                                .add_enabled(max.is_some(), egui::Button::new("Heal"))
+                                .on_disabled_hover_text("Open the project to know max HP and SP")
+                                .clicked()
+                                && let Some((hp, sp)) = max
+                            {
+                                actor.hp = hp;
+                                actor.sp = sp;
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        egui::CollapsingHeader::new("Switches").show(ui, |ui| {
+            let names: HashMap<_, _> = project
+                .map_or_default(|project| project.switch_names())
+                .into_iter()
+                .collect();
+            for (index, on) in editor.switches.iter_mut().enumerate() {
+                let id = index as u32 + 1;
+                let label = named("S", id, names.get(&id).copied(), encoding);
+                if matches(&label) {
+                    ui.checkbox(on, label);
+                }
+            }
+        });
+
+        egui::CollapsingHeader::new("Variables").show(ui, |ui| {
+            let names: HashMap<_, _> = project
+                .map_or_default(|project| project.variable_names())
+                .into_iter()
+                .collect();
+            egui::Grid::new("save editor variables").show(ui, |ui| {
+                for (index, value) in editor.variables.iter_mut().enumerate() {
+                    let id = index as u32 + 1;
+                    let label = named("V", id, names.get(&id).copied(), encoding);
+                    if matches(&label) {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(value));
+                        ui.end_row();
+                    }
+                }
+            });
+        });
+    });
+
+    changed
+}
@@ -0,0 +1,36 @@
+//! Where things are on RPG Maker 2000/2003 chipset and charset sheets.
+
+/// Size of a chipset tile in pixels.
+pub const TILE: u32 = 16;
+
+/// Size of a single charset frame in pixels.
+pub const FRAME_WIDTH: u32 = 24;
+pub const FRAME_HEIGHT: u32 = 32;
+
+/// Top-left pixel of a charset frame. A sheet holds 4×2 characters of 3 patterns × 4 directions.
+pub fn charset_frame(index: u32, direction: u32, pattern: u32) -> (u32, u32) {
+    (
+        (index % 4) * FRAME_WIDTH * 3 + pattern * FRAME_WIDTH,
+        (index / 4) * FRAME_HEIGHT * 4 + direction * FRAME_HEIGHT,
+    )
+}
+
+/// Top-left pixel of a lower layer tile (block E), by its index from 0 to 143.
+pub fn lower_tile(index: u32) -> (u32, u32) {
+    let (column, row) = if index < 96 {
+        (12 + index % 6, index / 6)
+    } else {
+        (18 + (index - 96) % 6, (index - 96) / 6)
+    };
+    (column * TILE, row * TILE)
+}
+
+/// Top-left pixel of an upper layer tile (block F), by its index from 0 to 143.
+pub fn upper_tile(index: u32) -> (u32, u32) {
+    let (column, row) = if index < 48 {
+        (18 + index % 6, 8 + index / 6)
+    } else {
+        (24 + (index - 48) % 6, (index - 48) / 6)
+    };
+    (column * TILE, row * TILE)
+}
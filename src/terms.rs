@@ -0,0 +1,214 @@
+//! What each entry of the database's vocabulary is used for.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    Battle,
+    Shops,
+    Inns,
+    Items,
+    BattleCommands,
+    MenuCommands,
+    Status,
+    Equipment,
+    SaveAndQuit,
+    Other,
+}
+
+pub const CATEGORIES: &[Category] = &[
+    Category::Battle,
+    Category::Shops,
+    Category::Inns,
+    Category::Items,
+    Category::BattleCommands,
+    Category::MenuCommands,
+    Category::Status,
+    Category::Equipment,
+    Category::SaveAndQuit,
+    Category::Other,
+];
+
+impl Category {
+    pub const fn to_str(self) -> &'static str {
+        match self {
+            Self::Battle => "Battle Messages",
+            Self::Shops => "Shop Messages",
+            Self::Inns => "Inn Messages",
+            Self::Items => "Item Menu",
+            Self::BattleCommands => "Battle Commands",
+            Self::MenuCommands => "Menu Commands",
+            Self::Status => "Status",
+            Self::Equipment => "Equipment",
+            Self::SaveAndQuit => "Save and Quit",
+            Self::Other => "Other",
+        }
+    }
+
+    pub fn of(id: u32) -> Self {
+        match id {
+            1..=39 => Self::Battle,
+            41..=77 => Self::Shops,
+            80..=89 => Self::Inns,
+            92..=95 => Self::Items,
+            101..=105 => Self::BattleCommands,
+            106..=122 => Self::MenuCommands,
+            123..=135 => Self::Status,
+            136..=140 => Self::Equipment,
+            146..=153 => Self::SaveAndQuit,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// What a term is shown for, with the placeholders the engine fills in. Covers every term of
+/// RPG Maker 2000 and 2003, marking those only 2003 has.
+pub fn role(id: u32) -> Option<&'static str> {
+    Some(match id {
+        1 => "Enemy appears (%S enemy)",
+        2 => "Preemptive attack",
+        3 => "Escaped",
+        4 => "Failed to escape",
+        5 => "Victory",
+        6 => "Defeat",
+        7 => "Experience gained (%V value, %U unit)",
+        8 => "Money gained, before the amount",
+        9 => "Money gained, after the amount",
+        10 => "Item found (%S item)",
+        11 => "Normal attack (%S attacker)",
+        12 => "Critical hit by actor (%S attacker, %O target)",
+        13 => "Critical hit by enemy (%S attacker, %O target)",
+        14 => "Defending (%S)",
+        15 => "Enemy waits (%S enemy)",
+        16 => "Enemy charges (%S enemy)",
+        17 => "Enemy self-destructs (%S enemy)",
+        18 => "Enemy escapes (%S enemy)",
+        19 => "Enemy transforms (%S old name, %O new name)",
+        20 => "Damage to enemy (%S enemy, %V value, %U unit)",
+        21 => "No damage to enemy (%S enemy)",
+        22 => "Damage to actor (%S actor, %V value, %U unit)",
+        23 => "No damage to actor (%S actor)",
+        24 => "Skill failed A (%S attacker, %O target)",
+        25 => "Skill failed B (%S attacker, %O target)",
+        26 => "Skill failed C (%S attacker, %O target)",
+        27 => "Attack dodged (%S attacker, %O target)",
+        28 => "Item used (%S user, %O item)",
+        29 => "HP/SP recovered (%S target, %V value, %U stat)",
+        30 => "Stat raised (%S target, %V value, %U stat)",
+        31 => "Stat lowered (%S target, %V value, %U stat)",
+        32 => "Absorbed by actor (%S attacker, %O target, %V value, %U stat)",
+        33 => "Absorbed by enemy (%S attacker, %O target, %V value, %U stat)",
+        34 => "Attribute resistance raised (%S target, %O attribute)",
+        35 => "Attribute resistance lowered (%S target, %O attribute)",
+        36 => "Level up (%S actor, %V level, %U unit)",
+        37 => "Skill learned (%S actor, %O skill)",
+        38 => "Battle starts (2003)",
+        39 => "Miss (2003)",
+        41 | 54 | 67 => "Greeting",
+        42 | 55 | 68 => "Anything else?",
+        43 | 56 | 69 => "Buy",
+        44 | 57 | 70 => "Sell",
+        45 | 58 | 71 => "Leave",
+        46 | 59 | 72 => "Which item to buy?",
+        47 | 60 | 73 => "How many to buy?",
+        48 | 61 | 74 => "Bought",
+        49 | 62 | 75 => "Which item to sell?",
+        50 | 63 | 76 => "How many to sell?",
+        51 | 64 | 77 => "Sold",
+        80 | 85 => "Greeting, before the price",
+        81 | 86 => "Greeting, after the price",
+        82 | 87 => "Would you like to stay?",
+        83 | 88 => "Stay",
+        84 | 89 => "Leave",
+        92 => "Items owned",
+        93 => "Items equipped",
+        95 => "Currency",
+        101 => "Fight",
+        102 => "Auto battle",
+        103 => "Escape",
+        104 => "Attack",
+        105 => "Defend",
+        106 => "Items",
+        107 => "Skills",
+        108 => "Equipment",
+        110 => "Save",
+        112 => "Quit",
+        114 => "New game",
+        115 => "Continue",
+        117 => "Shutdown",
+        118 => "Status (2003)",
+        119 => "Row (2003)",
+        120 => "Order (2003)",
+        121 => "ATB wait mode (2003)",
+        122 => "ATB active mode (2003)",
+        123 => "Level",
+        124 => "HP",
+        125 => "SP",
+        126 => "Normal status",
+        127 => "Experience (short)",
+        128 => "Level (short)",
+        129 => "HP (short)",
+        130 => "SP (short)",
+        131 => "SP cost",
+        132 => "Attack",
+        133 => "Defense",
+        134 => "Spirit",
+        135 => "Agility",
+        136 => "Weapon",
+        137 => "Shield",
+        138 => "Armor",
+        139 => "Helmet",
+        140 => "Accessory",
+        146 => "Save to which slot?",
+        147 => "Load which slot?",
+        148 => "File",
+        151 => "Quit the game?",
+        152 => "Yes",
+        153 => "No",
+        _ => return None,
+    })
+}
+
+/// The shop or inn a message belongs to, as the editor labels its three shop and two inn types.
+pub fn variant(id: u32) -> Option<&'static str> {
+    match id {
+        41..=51 => Some("Shop A"),
+        54..=64 => Some("Shop B"),
+        67..=77 => Some("Shop C"),
+        80..=84 => Some("Inn A"),
+        85..=89 => Some("Inn B"),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Issue {
+    Empty,
+    /// The text reads as Japanese, which in a translated game means it was missed.
+    Japanese,
+}
+
+impl Issue {
+    pub const fn to_str(self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Japanese => "untranslated",
+        }
+    }
+}
+
+/// Whether a term is empty or still in Japanese. Games in Shift JIS are Japanese to begin with,
+/// so nothing is flagged there.
+pub fn issue(bytes: &[u8], encoding: crate::code_page::CodePage) -> Option<Issue> {
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Some(Issue::Empty);
+    }
+    if encoding == crate::code_page::CodePage::ShiftJIS {
+        return None;
+    }
+    let (text, had_errors) = encoding_rs::SHIFT_JIS.decode_without_bom_handling(bytes);
+    // Kana only appear in Japanese, unlike the kanji Chinese text shares.
+    (!had_errors
+        && text
+            .chars()
+            .any(|char| ('\u{3040}'..='\u{30FF}').contains(&char)))
+    .then_some(Issue::Japanese)
+}
@@ -0,0 +1,422 @@
+//! Round trips through the Tiled map editor: maps become `.tmx` files, chipsets `.tsx` tilesets.
+//!
+//! Tiled can only place whole tiles, so each tileset holds one tile per palette entry, lower
+//! layer tiles first and then upper layer tiles. Water and autotiles lose their neighbour
+//! variant on the way out. Importing keeps the original variant wherever a cell still uses the
+//! same palette entry, and picks it from the neighbours again around cells that changed.
+
+use std::{collections::BTreeSet, fmt::Write, path::Path};
+
+use lcf::raw::lmu::{LcfMapUnitChunk, RawLcfMapUnit};
+
+use crate::{
+    assets::Assets,
+    chipset::{COLUMNS, LOWER_TILES, UPPER_TILES},
+    project::Project,
+    sheet::TILE,
+};
+
+/// Tiled numbers tiles from 1, leaving 0 for empty cells.
+const FIRST_GID: u32 = 1;
+
+/// The tileset tile a map tile is shown with.
+fn gid(tile: u16) -> u32 {
+    if let Some(index) = crate::chipset::upper_index(tile) {
+        FIRST_GID + (LOWER_TILES + index) as u32
+    } else if let Some(index) = crate::chipset::lower_index(tile) {
+        FIRST_GID + index as u32
+    } else {
+        0
+    }
+}
+
+/// The map tile for a tileset tile. `original` is kept when it is drawn with the same one.
+fn tile(gid: u32, original: u16, upper: bool) -> Result<u16, String> {
+    // Erased upper layer cells become the blank tile. The lower layer can't be blank, and tiles
+    // the tileset doesn't cover were exported as empty cells, so those come back unchanged.
+    if gid == 0 && upper && self::gid(original) != 0 {
+        return Ok(10000);
+    }
+    if gid == 0 || gid == self::gid(original) {
+        return Ok(original);
+    }
+    let index = (gid - FIRST_GID) as usize;
+    match index.checked_sub(LOWER_TILES) {
+        None => Ok(crate::chipset::lower_tile(index)),
+        Some(index) if index < UPPER_TILES => Ok(10000 + index as u16),
+        Some(_) => Err(format!("tile {gid} is not in the chipset tileset")),
+    }
+}
+
+/// Writes the chipset tileset image and `.tsx` for a chipset into `folder`, returning the
+/// name of the `.tsx`.
+pub fn export_tileset(
+    project: &Project,
+    id: u32,
+    folder: &Path,
+    assets: &mut Assets,
+    encoding: crate::code_page::CodePage,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let file = project
+        .chipset_file(id)
+        .ok_or_else(|| format!("chipset {id} is not in the database"))?;
+    let chipset = assets
+        .image("ChipSet", file, encoding)
+        .ok_or_else(|| format!("chipset {id} has no image"))?;
+    let tables = project.chipset(id).unwrap_or_default();
+
+    let lower = crate::render::palette(&chipset, false, Default::default());
+    let upper = crate::render::palette(&chipset, true, Default::default());
+    let mut image = image::RgbaImage::new(lower.width(), lower.height() + upper.height());
+    image::imageops::replace(&mut image, &lower, 0, 0);
+    image::imageops::replace(&mut image, &upper, 0, lower.height() as i64);
+    let image_name = format!("ChipSet{id:04}.png");
+    image.save_with_format(folder.join(&image_name), image::ImageFormat::Png)?;
+
+    let name = encoding
+        .to_encoding()
+        .decode(project.chipset_name(id).unwrap_or_default())
+        .0;
+    let mut tsx = String::new();
+    writeln!(tsx, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        tsx,
+        r#"<tileset version="1.10" name="{}" tilewidth="{TILE}" tileheight="{TILE}" tilecount="{}" columns="{COLUMNS}">"#,
+        escape(&name),
+        LOWER_TILES + UPPER_TILES,
+    )?;
+    writeln!(
+        tsx,
+        r#" <image source="{image_name}" width="{}" height="{}"/>"#,
+        image.width(),
+        image.height()
+    )?;
+    for index in 0..LOWER_TILES + UPPER_TILES {
+        let upper = index >= LOWER_TILES;
+        let passability = if upper {
+            tables.upper.get(index - LOWER_TILES)
+        } else {
+            tables.lower.get(index)
+        };
+        writeln!(tsx, r#" <tile id="{index}">"#)?;
+        writeln!(tsx, "  <properties>")?;
+        if let Some(passability) = passability {
+            writeln!(
+                tsx,
+                r#"   <property name="passability" type="int" value="{}"/>"#,
+                passability.0
+            )?;
+            writeln!(
+                tsx,
+                r#"   <property name="passable" value="{}"/>"#,
+                escape(&passability.label())
+            )?;
+        }
+        if !upper && let Some(terrain) = tables.terrain.get(index) {
+            writeln!(
+                tsx,
+                r#"   <property name="terrain" type="int" value="{terrain}"/>"#
+            )?;
+        }
+        writeln!(tsx, "  </properties>")?;
+        writeln!(tsx, " </tile>")?;
+    }
+    writeln!(tsx, "</tileset>")?;
+
+    let tsx_name = format!("ChipSet{id:04}.tsx");
+    std::fs::write(folder.join(&tsx_name), tsx)?;
+    Ok(tsx_name)
+}
+
+/// Writes a map unit as `<name>.tmx` into `folder`, along with the tileset of its chipset.
+pub fn export(
+    project: &Project,
+    map_unit: &lcf::lmu::LcfMapUnit,
+    name: &str,
+    folder: &Path,
+    assets: &mut Assets,
+    encoding: crate::code_page::CodePage,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tileset = export_tileset(project, map_unit.chipset, folder, assets, encoding)?;
+    let (width, height) = (map_unit.width, map_unit.height);
+
+    let mut tmx = String::new();
+    writeln!(tmx, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        tmx,
+        r#"<map version="1.10" orientation="orthogonal" renderorder="right-down" width="{width}" height="{height}" tilewidth="{TILE}" tileheight="{TILE}" infinite="0" nextlayerid="4" nextobjectid="{}">"#,
+        map_unit.events.len() + 1
+    )?;
+    writeln!(tmx, " <properties>")?;
+    writeln!(
+        tmx,
+        r#"  <property name="chipset" type="int" value="{}"/>"#,
+        map_unit.chipset
+    )?;
+    writeln!(tmx, " </properties>")?;
+    writeln!(
+        tmx,
+        r#" <tileset firstgid="{FIRST_GID}" source="{tileset}"/>"#
+    )?;
+
+    for (id, layer_name, layer) in [(1, "Lower", &map_unit.lower), (2, "Upper", &map_unit.upper)] {
+        writeln!(
+            tmx,
+            r#" <layer id="{id}" name="{layer_name}" width="{width}" height="{height}">"#
+        )?;
+        writeln!(tmx, r#"  <data encoding="csv">"#)?;
+        let rows = layer
+            .chunks(width.max(1) as usize)
+            .map(|row| {
+                row.iter()
+                    .map(|tile| gid(*tile).to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        writeln!(tmx, "{}", rows.join(",\n"))?;
+        writeln!(tmx, "  </data>")?;
+        writeln!(tmx, " </layer>")?;
+    }
+
+    writeln!(tmx, r#" <objectgroup id="3" name="Events">"#)?;
+    for (index, event) in map_unit.events.iter().enumerate() {
+        writeln!(
+            tmx,
+            r#"  <object id="{}" name="{}" x="{}" y="{}" width="{TILE}" height="{TILE}">"#,
+            index + 1,
+            escape(&encoding.to_encoding().decode(&event.name).0),
+            event.x * TILE,
+            event.y * TILE,
+        )?;
+        writeln!(tmx, "   <properties>")?;
+        writeln!(
+            tmx,
+            r#"    <property name="id" type="int" value="{}"/>"#,
+            event.id
+        )?;
+        for (page, item) in event.pages.iter().enumerate() {
+            let trigger = crate::labels::trigger(item.trigger);
+            writeln!(
+                tmx,
+                r#"    <property name="page {} trigger" value="{trigger}"/>"#,
+                page + 1
+            )?;
+        }
+        writeln!(tmx, "   </properties>")?;
+        writeln!(tmx, "  </object>")?;
+    }
+    writeln!(tmx, " </objectgroup>")?;
+    writeln!(tmx, "</map>")?;
+
+    std::fs::write(folder.join(format!("{name}.tmx")), tmx)?;
+    Ok(())
+}
+
+/// Replaces the lower and upper layers of a map unit with those of a `.tmx` written by
+/// [`export`]. Tiled has to keep saving layers as CSV for this to work.
+pub fn import(
+    tmx: &str,
+    map_unit: &mut RawLcfMapUnit,
+    original: &lcf::lmu::LcfMapUnit,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let size = (original.width * original.height) as usize;
+    let mut lower = read_layer(tmx, "Lower", &original.lower, size)?;
+    connect(&mut lower, &original.lower, original.width as usize);
+    let upper = read_layer(tmx, "Upper", &original.upper, size)?;
+
+    for chunk in &mut map_unit.0.inner_vec {
+        match &mut chunk.data {
+            LcfMapUnitChunk::Lower(tiles) => *tiles = lower.clone(),
+            LcfMapUnitChunk::Upper(tiles) => *tiles = upper.clone(),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Imports a `.tmx` into the `.lmu` at `path`, keeping the previous file as `.lmu.bak`.
+pub fn import_file(
+    tmx: &Path,
+    path: &Path,
+    map_unit: &mut RawLcfMapUnit,
+    original: &lcf::lmu::LcfMapUnit,
+) -> Result<(), Box<dyn std::error::Error>> {
+    import(&std::fs::read_to_string(tmx)?, map_unit, original)?;
+
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    map_unit.write(&mut cursor)?;
+    std::fs::copy(path, path.with_extension("lmu.bak"))?;
+    std::fs::write(path, cursor.into_inner())?;
+    Ok(())
+}
+
+fn read_layer(
+    tmx: &str,
+    name: &str,
+    original: &[u16],
+    size: usize,
+) -> Result<Vec<u16>, Box<dyn std::error::Error>> {
+    let start = tmx
+        .match_indices("<layer ")
+        .map(|(start, _)| start)
+        .find(|start| {
+            let tag = &tmx[*start..];
+            tag[..tag.find('>').unwrap_or(tag.len())].contains(&format!(r#"name="{name}""#))
+        })
+        .ok_or_else(|| format!("layer {name} is missing"))?;
+    let data = &tmx[start..];
+    let data = &data[data
+        .find("<data")
+        .ok_or_else(|| format!("layer {name} has no data"))?..];
+    if !data[..data.find('>').unwrap_or(0)].contains(r#"encoding="csv""#) {
+        return Err(format!("layer {name} is not saved as CSV").into());
+    }
+    let data = &data[data.find('>').map_or(0, |end| end + 1)..];
+    let data = &data[..data
+        .find("</data>")
+        .ok_or_else(|| format!("layer {name} is not closed"))?];
+
+    let gids = data
+        .split(',')
+        .map(|gid| gid.trim().parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    if gids.len() != size {
+        return Err(format!("layer {name} has {} tiles instead of {size}", gids.len()).into());
+    }
+
+    gids.iter()
+        .zip(original)
+        // Tiled keeps flip flags in the top bits, which have no meaning here.
+        .map(|(gid, original)| {
+            tile(gid & 0x0FFF_FFFF, *original, name == "Upper").map_err(Into::into)
+        })
+        .collect()
+}
+
+/// Picks the neighbour variant of the water and autotile cells in and around those that changed,
+/// as the editor does when painting, since Tiled only places the first variant of each.
+fn connect(layer: &mut [u16], original: &[u16], width: usize) {
+    let width = width.max(1);
+    let height = layer.len() / width;
+    let neighbour = |cell: usize, (dx, dy): (isize, isize)| {
+        let x = (cell % width)
+            .checked_add_signed(dx)
+            .filter(|x| *x < width)?;
+        let y = (cell / width)
+            .checked_add_signed(dy)
+            .filter(|y| *y < height)?;
+        Some(y * width + x)
+    };
+    let around = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+
+    let cells: BTreeSet<usize> = (0..layer.len())
+        .filter(|cell| layer[*cell] != original[*cell])
+        .flat_map(|cell| {
+            std::iter::once(cell)
+                .chain(around.iter().filter_map(move |step| neighbour(cell, *step)))
+        })
+        .collect();
+    // The kind of tile a cell holds doesn't depend on its variant, so neighbours can be read
+    // from the layer as it was.
+    let before = layer.to_vec();
+    for cell in cells {
+        let tile = before[cell];
+        // Cells beyond the edge of the map join everything.
+        let neighbours = around.map(|step| neighbour(cell, step).map(|cell| before[cell]));
+        layer[cell] = match tile {
+            0..3000 => {
+                let deep = tile >= 2000;
+                let joins = neighbours.map(|other| other.is_none_or(|other| other < 3000));
+                // Open water next to water of the other depth takes the transition of block B,
+                // for each quarter from the three neighbours at its corner.
+                let other_depth = neighbours.map(|other| {
+                    other.is_some_and(|other| other < 3000 && (other >= 2000) != deep)
+                });
+                let quarters = [[7, 0, 6], [1, 0, 2], [5, 4, 6], [3, 4, 2]];
+                let transitions = quarters
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, sides)| sides.iter().any(|side| other_depth[*side]))
+                    .fold(0, |bits, (quarter, _)| bits | 1 << quarter);
+                tile / 1000 * 1000 + transitions * 50 + crate::chipset::neighbour_variant(joins)
+            }
+            4000..4600 => {
+                let block = (tile - 4000) / 50;
+                let joins = neighbours.map(|other| {
+                    other.is_none_or(|other| {
+                        (4000..4600).contains(&other) && (other - 4000) / 50 == block
+                    })
+                });
+                4000 + block * 50 + crate::chipset::neighbour_variant(joins)
+            }
+            _ => tile,
+        };
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tmx(name: &str, csv: &str) -> String {
+        format!(
+            r#"<map><layer id="1" name="{name}" width="2" height="1"><data encoding="csv">{csv}</data></layer></map>"#
+        )
+    }
+
+    #[test]
+    fn ignores_flip_flags() {
+        let flipped = (gid(5000) | 0x8000_0000).to_string();
+        let layer = read_layer(
+            &tmx("Lower", &format!("{flipped},0")),
+            "Lower",
+            &[5001, 5001],
+            2,
+        );
+        assert_eq!(layer.unwrap(), [5000, 5001]);
+    }
+
+    #[test]
+    fn erased_upper_cells_become_blank() {
+        let layer = read_layer(&tmx("Upper", "0,0"), "Upper", &[10005, 9999], 2);
+        // Cells the tileset doesn't cover were exported empty, so they stay as they were.
+        assert_eq!(layer.unwrap(), [10000, 9999]);
+    }
+
+    #[test]
+    fn rejects_a_layer_of_the_wrong_size() {
+        let err = read_layer(&tmx("Lower", "19,19"), "Lower", &[5000; 3], 3).unwrap_err();
+        assert_eq!(err.to_string(), "layer Lower has 2 tiles instead of 3");
+    }
+
+    #[test]
+    fn connects_painted_autotiles() {
+        let original = [5000; 9];
+        let mut layer = original;
+        layer[4] = 4000;
+        connect(&mut layer, &original, 3);
+        // Nothing around it is the same autotile, so every side gets an edge.
+        assert_eq!(layer[4], 4046);
+
+        let mut layer = [4000; 9];
+        connect(&mut layer, &original, 3);
+        assert_eq!(layer, [4000; 9]);
+    }
+}
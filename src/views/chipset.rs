@@ -0,0 +1,406 @@
+use crate::{
+    assets::Assets,
+    chipset::{COLUMNS, ChipSet, LOWER_TILES, Passability, UPPER_TILES},
+    project::{Location, Project},
+    render::Frame,
+    sheet::TILE,
+};
+
+/// What is drawn on top of tiles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Overlay {
+    #[default]
+    None,
+    Passability,
+    Terrain,
+}
+
+pub const OVERLAYS: &[Overlay] = &[Overlay::None, Overlay::Passability, Overlay::Terrain];
+
+impl Overlay {
+    pub const fn to_str(self) -> &'static str {
+        match self {
+            Self::None => "No Overlay",
+            Self::Passability => "Passability",
+            Self::Terrain => "Terrain",
+        }
+    }
+
+    pub fn combo_box(&mut self, ui: &mut egui::Ui, id: impl std::hash::Hash) {
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(self.to_str())
+            .show_ui(ui, |ui| {
+                for overlay in OVERLAYS {
+                    ui.selectable_value(self, *overlay, overlay.to_str());
+                }
+            });
+    }
+}
+
+/// Draws the passability or terrain of a single tile over `rect`.
+pub fn draw_overlay(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    overlay: Overlay,
+    passability: Option<Passability>,
+    terrain: Option<u16>,
+) {
+    let font = egui::FontId::proportional(rect.height() * 0.6);
+    match overlay {
+        Overlay::None => (),
+        Overlay::Passability => {
+            let Some(passability) = passability else {
+                return;
+            };
+            let blocked = egui::Stroke::new(2.0, egui::Color32::RED);
+            match passability.0 & 0x0F {
+                0x0F => {
+                    painter.circle_stroke(
+                        rect.center(),
+                        rect.height() * 0.25,
+                        egui::Stroke::new(1.5, egui::Color32::LIGHT_GREEN),
+                    );
+                }
+                0 => {
+                    let inner = rect.shrink(rect.height() * 0.25);
+                    painter.line_segment([inner.left_top(), inner.right_bottom()], blocked);
+                    painter.line_segment([inner.right_top(), inner.left_bottom()], blocked);
+                }
+                _ => {
+                    let inner = rect.shrink(1.0);
+                    for (flag, edge) in [
+                        (
+                            Passability::DOWN,
+                            [inner.left_bottom(), inner.right_bottom()],
+                        ),
+                        (Passability::LEFT, [inner.left_top(), inner.left_bottom()]),
+                        (
+                            Passability::RIGHT,
+                            [inner.right_top(), inner.right_bottom()],
+                        ),
+                        (Passability::UP, [inner.left_top(), inner.right_top()]),
+                    ] {
+                        if !passability.contains(flag) {
+                            painter.line_segment(edge, blocked);
+                        }
+                    }
+                }
+            }
+
+            let marks = [
+                (Passability::STAR, "★"),
+                (Passability::SQUARE, "□"),
+                (Passability::COUNTER, "◇"),
+            ]
+            .into_iter()
+            .filter(|(flag, _)| passability.contains(*flag))
+            .map(|(_, mark)| mark)
+            .collect::<String>();
+            if !marks.is_empty() {
+                painter.text(
+                    rect.right_top(),
+                    egui::Align2::RIGHT_TOP,
+                    marks,
+                    egui::FontId::proportional(rect.height() * 0.4),
+                    egui::Color32::YELLOW,
+                );
+            }
+        }
+        Overlay::Terrain => {
+            let Some(terrain) = terrain else {
+                return;
+            };
+            let hue = (terrain as f32 * 0.618_034).fract();
+            painter.rect_filled(rect, 0.0, egui::ecolor::Hsva::new(hue, 0.8, 0.8, 0.35));
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                terrain.to_string(),
+                font,
+                egui::Color32::WHITE,
+            );
+        }
+    }
+}
+
+/// Draws the lower or upper layer tiles of a chipset in the editor's palette order, with an
+/// overlay on top. Returns the palette's response and the index of the tile under the pointer.
+pub fn palette(
+    ui: &mut egui::Ui,
+    texture: &egui::TextureHandle,
+    tables: &ChipSet,
+    overlay: Overlay,
+    (upper, selected): (bool, Option<usize>),
+    zoom: f32,
+) -> (egui::Response, Option<usize>) {
+    let (rect, response) = ui.allocate_exact_size(texture.size_vec2() * zoom, egui::Sense::click());
+    let painter = ui.painter_at(rect);
+    painter.image(
+        texture.id(),
+        rect,
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+        egui::Color32::WHITE,
+    );
+
+    let size = TILE as f32 * zoom;
+    let count = if upper { UPPER_TILES } else { LOWER_TILES };
+    for index in 0..count {
+        let tile = egui::Rect::from_min_size(
+            rect.min + egui::vec2((index % COLUMNS) as f32, (index / COLUMNS) as f32) * size,
+            egui::Vec2::splat(size),
+        );
+        let (passability, terrain) = if upper {
+            (tables.upper.get(index).copied(), None)
+        } else {
+            (
+                tables.lower.get(index).copied(),
+                tables.terrain.get(index).copied(),
+            )
+        };
+        draw_overlay(&painter, tile, overlay, passability, terrain);
+        if selected == Some(index) {
+            painter.rect_stroke(
+                tile,
+                0.0,
+                egui::Stroke::new(2.0, egui::Color32::WHITE),
+                egui::StrokeKind::Inside,
+            );
+        }
+    }
+
+    let index = response
+        .hover_pos()
+        .map(|position| {
+            let position = position - rect.min;
+            (position.y / size) as usize * COLUMNS + (position.x / size) as usize
+        })
+        .filter(|index| *index < count);
+    (response, index)
+}
+
+/// Both palettes of a chipset side by side, with a picker for the overlay.
+pub fn preview(
+    ui: &mut egui::Ui,
+    assets: &mut Assets,
+    file: &[u8],
+    tables: &ChipSet,
+    encoding: crate::code_page::CodePage,
+    id: egui::Id,
+) {
+    let (Some(lower), Some(upper)) = (
+        assets.palette(ui.ctx(), file, false, Frame::default(), encoding),
+        assets.palette(ui.ctx(), file, true, Frame::default(), encoding),
+    ) else {
+        ui.label("Chipset not found");
+        return;
+    };
+
+    let mut overlay = ui.data(|data| data.get_temp(id).unwrap_or(Overlay::Passability));
+    ui.vertical(|ui| {
+        overlay.combo_box(ui, id);
+        ui.horizontal_top(|ui| {
+            for (texture, upper) in [(lower, false), (upper, true)] {
+                let (response, index) = palette(ui, &texture, tables, overlay, (upper, None), 2.0);
+                if let Some(index) = index {
+                    let passability = if upper {
+                        tables.upper.get(index)
+                    } else {
+                        tables.lower.get(index)
+                    };
+                    let terrain = (!upper).then(|| tables.terrain.get(index)).flatten();
+                    response.on_hover_ui_at_pointer(|ui| {
+                        ui.label(format!("Tile {index}"));
+                        if let Some(passability) = passability {
+                            ui.label(format!("Passability: {}", passability.label()));
+                        }
+                        if let Some(terrain) = terrain {
+                            ui.label(format!("Terrain: {terrain}"));
+                        }
+                    });
+                }
+            }
+        });
+    });
+    ui.data_mut(|data| data.insert_temp(id, overlay));
+}
+
+/// The chipset window: both palettes with their sections, and the tile last clicked.
+pub struct ChipSetView {
+    pub id: u32,
+    pub overlay: Overlay,
+    pub playing: bool,
+    frame: Frame,
+    selected: Option<(bool, usize)>,
+    /// Maps using the selected tile, found when it is clicked.
+    maps: Vec<u32>,
+}
+
+impl ChipSetView {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            overlay: Overlay::Passability,
+            playing: true,
+            frame: Frame::default(),
+            selected: None,
+            maps: Vec::new(),
+        }
+    }
+}
+
+/// Draws the chipset window, returning the map that was clicked in the inspector.
+pub fn show(
+    ui: &mut egui::Ui,
+    view: &mut ChipSetView,
+    project: &Project,
+    assets: &mut Assets,
+    encoding: crate::code_page::CodePage,
+) -> Option<Location> {
+    let tables = project.chipset(view.id).unwrap_or_default();
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("chipset")
+            .selected_text(chipset_label(project, view.id, encoding))
+            .show_ui(ui, |ui| {
+                for id in project.chipset_ids() {
+                    if ui
+                        .selectable_label(view.id == id, chipset_label(project, id, encoding))
+                        .clicked()
+                        && view.id != id
+                    {
+                        *view = ChipSetView {
+                            overlay: view.overlay,
+                            playing: view.playing,
+                            ..ChipSetView::new(id)
+                        };
+                    }
+                }
+            });
+        view.overlay.combo_box(ui, "chipset overlay");
+        playback(ui, &mut view.playing, &mut view.frame, &tables);
+    });
+
+    let textures = project.chipset_file(view.id).and_then(|file| {
+        Some((
+            assets.palette(ui.ctx(), file, false, view.frame, encoding)?,
+            assets.palette(ui.ctx(), file, true, view.frame, encoding)?,
+        ))
+    });
+    let Some((lower, upper)) = textures else {
+        ui.label("Chipset not found");
+        return None;
+    };
+
+    let mut clicked = None;
+    ui.horizontal_top(|ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.horizontal_top(|ui| {
+                for (texture, upper) in [(lower, false), (upper, true)] {
+                    sections(ui, upper, 2.0);
+                    let selected = view
+                        .selected
+                        .filter(|(layer, _)| *layer == upper)
+                        .map(|(_, index)| index);
+                    let (response, index) =
+                        palette(ui, &texture, &tables, view.overlay, (upper, selected), 2.0);
+                    if let Some(index) = index
+                        && response.clicked()
+                    {
+                        view.selected = Some((upper, index));
+                        view.maps = project.maps_using_tile(view.id, upper, index);
+                    }
+                }
+            });
+        });
+
+        ui.separator();
+        ui.vertical(|ui| {
+            let Some((upper, index)) = view.selected else {
+                ui.label("Click a tile to inspect it.");
+                return;
+            };
+            let ids = crate::chipset::tile_ids(upper, index);
+            ui.label(format!(
+                "Section {}, tile {index}",
+                crate::chipset::section(upper, index)
+            ));
+            if ids.start() == ids.end() {
+                ui.label(format!("Tile ID: {}", ids.start()));
+            } else {
+                ui.label(format!("Tile IDs: {}–{}", ids.start(), ids.end()));
+            }
+
+            let passability = if upper {
+                tables.upper.get(index)
+            } else {
+                tables.lower.get(index)
+            };
+            if let Some(passability) = passability {
+                ui.label(format!("Passability: {}", passability.label()));
+            }
+            if !upper && let Some(terrain) = tables.terrain.get(index) {
+                ui.label(format!("Terrain: {terrain}"));
+            }
+
+            ui.separator();
+            ui.label(format!("Used by {} maps", view.maps.len()));
+            egui::ScrollArea::vertical().id_salt("maps").show(ui, |ui| {
+                for &map in &view.maps {
+                    let name = project
+                        .map_name(map)
+                        .map(|name| encoding.to_encoding().decode(name).0.into_owned())
+                        .unwrap_or_default();
+                    if ui.link(format!("Map{map:04} {name}")).clicked() {
+                        clicked = Some(Location::Map { map });
+                    }
+                }
+            });
+        });
+    });
+    clicked
+}
+
+fn chipset_label(project: &Project, id: u32, encoding: crate::code_page::CodePage) -> String {
+    let name = project.chipset_name(id).unwrap_or_default();
+    format!("{id:04}: {}", encoding.to_encoding().decode(name).0)
+}
+
+/// Letters of the chipset sheet sections, beside the palette rows they fill.
+fn sections(ui: &mut egui::Ui, upper: bool, zoom: f32) {
+    let size = TILE as f32 * zoom;
+    let rows: &[(&str, usize)] = if upper {
+        &[("F", 0)]
+    } else {
+        &[("A B C", 0), ("D", 1), ("E", 3)]
+    };
+    let count = if upper { UPPER_TILES } else { LOWER_TILES };
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(size * 2.0, count.div_ceil(COLUMNS) as f32 * size),
+        egui::Sense::hover(),
+    );
+    for (letters, row) in rows {
+        ui.painter().text(
+            rect.right_top() + egui::vec2(0.0, *row as f32 * size + size / 2.0),
+            egui::Align2::RIGHT_CENTER,
+            letters,
+            egui::FontId::proportional(size * 0.4),
+            ui.visuals().weak_text_color(),
+        );
+    }
+}
+
+/// A button to play or pause tile animations, advancing `frame` while they play.
+pub fn playback(ui: &mut egui::Ui, playing: &mut bool, frame: &mut Frame, tables: &ChipSet) {
+    let (icon, tooltip) = if *playing {
+        ("⏸", "Pause animations")
+    } else {
+        ("▶", "Play animations")
+    };
+    if ui.button(icon).on_hover_text(tooltip).clicked() {
+        *playing = !*playing;
+    }
+    if *playing {
+        *frame = tables.frame(ui.input(|input| input.time));
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_secs_f64(tables.frame_duration()));
+    }
+}
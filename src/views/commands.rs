@@ -0,0 +1,130 @@
+use lcf::raw::lmu::event::{command::Command, instruction::Instruction};
+
+/// Draws event commands nested by their indent, so branches, loops and choices fold.
+///
+/// Every command keeps the id `node + index`, whether it ends up as a leaf or a directory.
+pub fn update(
+    commands: &[Command],
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+    node: u64,
+) {
+    let mut index = 0;
+    while index < commands.len() {
+        index = update_command(commands, index, builder, encoding, node);
+    }
+}
+
+/// Draws one command and its children, returning the index of the next sibling.
+fn update_command(
+    commands: &[Command],
+    index: usize,
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+    node: u64,
+) -> usize {
+    let command = &commands[index];
+    let label = egui::RichText::new(format!(
+        "{index}: {:?} {}",
+        command.instruction,
+        encoding.to_encoding().decode(&command.string).0,
+    ))
+    .color(Category::of(&command.instruction).color());
+
+    let children = commands[index + 1..]
+        .iter()
+        .take_while(|child| child.indent > command.indent)
+        .count();
+    if children == 0 {
+        builder.leaf(node + index as u64, label);
+        return index + 1;
+    }
+
+    if builder.dir(node + index as u64, label) {
+        let mut child = index + 1;
+        while child <= index + children {
+            child = update_command(commands, child, builder, encoding, node);
+        }
+    }
+    builder.close_dir();
+    index + children + 1
+}
+
+/// Node ids leading to a command, relative to the ids given to [`update`].
+pub fn path(commands: &[Command], node: u64, index: usize) -> Vec<u64> {
+    let mut path = vec![node + index as u64];
+    let mut indent = commands.get(index).map_or_default(|command| command.indent);
+    for (parent, command) in commands[..index.min(commands.len())]
+        .iter()
+        .enumerate()
+        .rev()
+    {
+        if command.indent < indent {
+            path.push(node + parent as u64);
+            indent = command.indent;
+        }
+    }
+    path.reverse();
+    path
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    FlowControl,
+    Message,
+    Movement,
+    System,
+}
+
+impl Category {
+    pub fn of(instruction: &Instruction) -> Self {
+        match instruction {
+            Instruction::ConditionalBranch { .. }
+            | Instruction::ElseBranch
+            | Instruction::EndBranch
+            | Instruction::Loop
+            | Instruction::BreakLoop
+            | Instruction::EndLoop
+            | Instruction::Label { .. }
+            | Instruction::JumpToLabel { .. }
+            | Instruction::CallEvent { .. }
+            | Instruction::EndEventProcessing
+            | Instruction::EraseEvent
+            | Instruction::Wait { .. }
+            | Instruction::ShowChoiceOption { .. }
+            | Instruction::ShowChoiceEnd
+            | Instruction::End => Self::FlowControl,
+            Instruction::ShowMessage
+            | Instruction::ShowMessageNextLine
+            | Instruction::MessageOptions { .. }
+            | Instruction::ChangeFaceset { .. }
+            | Instruction::ShowChoice { .. }
+            | Instruction::InputNumber { .. }
+            | Instruction::Comment
+            | Instruction::CommentNextLine => Self::Message,
+            Instruction::TransferPlayer { .. }
+            | Instruction::GetPlayerLocation { .. }
+            | Instruction::MoveToVariableLocation { .. }
+            | Instruction::GetOnOffVehicle
+            | Instruction::SetVehicleLocation { .. }
+            | Instruction::SetEventLocation { .. }
+            | Instruction::SwapEventLocation { .. }
+            | Instruction::GetEventLocation { .. }
+            | Instruction::MoveEvent { .. }
+            | Instruction::WaitForAllMovement
+            | Instruction::HaltAllMovement
+            | Instruction::ShowHidePlayer { .. }
+            | Instruction::ScrollMap { .. } => Self::Movement,
+            _ => Self::System,
+        }
+    }
+
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            Self::FlowControl => egui::Color32::from_rgb(86, 156, 214),
+            Self::Message => egui::Color32::from_rgb(106, 170, 100),
+            Self::Movement => egui::Color32::from_rgb(206, 145, 80),
+            Self::System => egui::Color32::PLACEHOLDER,
+        }
+    }
+}
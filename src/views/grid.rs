@@ -0,0 +1,150 @@
+use crate::chipset::Tile;
+
+const CELL: egui::Vec2 = egui::vec2(44.0, 20.0);
+
+/// Settings of the tile grid window, and the cells selected in it.
+#[derive(Default)]
+pub struct GridView {
+    pub upper: bool,
+    /// The cell a drag started on and the one it is over now.
+    selection: Option<((u32, u32), (u32, u32))>,
+}
+
+impl GridView {
+    /// The selected cells as a rectangle of columns and rows, both inclusive.
+    fn selected(&self) -> Option<(std::ops::RangeInclusive<u32>, std::ops::RangeInclusive<u32>)> {
+        let ((x1, y1), (x2, y2)) = self.selection?;
+        Some((x1.min(x2)..=x1.max(x2), y1.min(y2)..=y1.max(y2)))
+    }
+}
+
+/// Draws one layer of a map unit as a grid of decoded tile ids.
+pub fn show(ui: &mut egui::Ui, view: &mut GridView, map_unit: &lcf::lmu::LcfMapUnit) {
+    let layer = if view.upper {
+        &map_unit.upper
+    } else {
+        &map_unit.lower
+    };
+
+    let mut copy = false;
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut view.upper, false, "Lower");
+        ui.selectable_value(&mut view.upper, true, "Upper");
+        ui.separator();
+        copy = ui
+            .add_enabled(view.selection.is_some(), egui::Button::new("Copy CSV"))
+            .clicked();
+        if let Some((columns, rows)) = view.selected() {
+            ui.label(format!(
+                "{}×{} from ({}, {})",
+                columns.end() - columns.start() + 1,
+                rows.end() - rows.start() + 1,
+                columns.start(),
+                rows.start()
+            ));
+        }
+    });
+    copy |= ui.ui_contains_pointer() && ui.input(|input| input.events.contains(&egui::Event::Copy));
+    if copy && let Some((columns, rows)) = view.selected() {
+        let csv = rows
+            .filter(|y| *y < map_unit.height)
+            .map(|y| {
+                columns
+                    .clone()
+                    .filter(|x| *x < map_unit.width)
+                    .filter_map(|x| layer.get((y * map_unit.width + x) as usize))
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        ui.ctx().copy_text(csv);
+    }
+
+    egui::ScrollArea::both().show(ui, |ui| {
+        let size = egui::vec2(map_unit.width as f32, map_unit.height as f32) * CELL;
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+        let cell_at = |position: egui::Pos2| {
+            let cell = ((position - rect.min) / CELL).floor();
+            (
+                (cell.x.max(0.0) as u32).min(map_unit.width.saturating_sub(1)),
+                (cell.y.max(0.0) as u32).min(map_unit.height.saturating_sub(1)),
+            )
+        };
+
+        if let Some(position) = response.interact_pointer_pos() {
+            let cell = cell_at(position);
+            if response.drag_started() || response.clicked() {
+                view.selection = Some((cell, cell));
+            } else if let Some((start, _)) = view.selection {
+                view.selection = Some((start, cell));
+            }
+        }
+
+        // Only the visible cells, as large maps have hundreds of thousands of them.
+        let visible = ui.clip_rect().intersect(rect);
+        let first = ((visible.min - rect.min) / CELL).floor();
+        let last = ((visible.max - rect.min) / CELL).ceil();
+        let selected = view.selected();
+        for y in first.y as u32..(last.y as u32).min(map_unit.height) {
+            for x in first.x as u32..(last.x as u32).min(map_unit.width) {
+                let Some(&tile) = layer.get((y * map_unit.width + x) as usize) else {
+                    continue;
+                };
+                let cell = egui::Rect::from_min_size(
+                    rect.min + egui::vec2(x as f32, y as f32) * CELL,
+                    CELL,
+                );
+                let tile = Tile::decode(tile);
+                painter.rect(
+                    cell.shrink(0.5),
+                    0.0,
+                    color(tile),
+                    egui::Stroke::NONE,
+                    egui::StrokeKind::Inside,
+                );
+                painter.text(
+                    cell.center(),
+                    egui::Align2::CENTER_CENTER,
+                    tile.short(),
+                    egui::FontId::monospace(10.0),
+                    ui.visuals().text_color(),
+                );
+                if selected
+                    .as_ref()
+                    .is_some_and(|(columns, rows)| columns.contains(&x) && rows.contains(&y))
+                {
+                    painter.rect_filled(
+                        cell,
+                        0.0,
+                        ui.visuals().selection.bg_fill.gamma_multiply(0.5),
+                    );
+                }
+            }
+        }
+
+        if let Some(position) = response.hover_pos() {
+            let (x, y) = cell_at(position);
+            if let Some(&tile) = layer.get((y * map_unit.width + x) as usize) {
+                response.on_hover_text_at_pointer(format!(
+                    "({x}, {y}): {tile}\n{}",
+                    Tile::decode(tile).describe()
+                ));
+            }
+        }
+    });
+}
+
+fn color(tile: Tile) -> egui::Color32 {
+    let hue = match tile {
+        Tile::WaterA { .. } | Tile::WaterB { .. } | Tile::DeepWater { .. } => 0.6,
+        Tile::Animated(_) => 0.75,
+        Tile::Autotile { .. } => 0.3,
+        Tile::Lower(_) => 0.1,
+        Tile::Upper(_) => 0.0,
+        Tile::Unknown(_) => return egui::Color32::TRANSPARENT,
+    };
+    egui::ecolor::Hsva::new(hue, 0.5, 0.6, 0.3).into()
+}
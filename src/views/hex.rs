@@ -0,0 +1,115 @@
+//! The bytes of an open file as hex and text, with those of the selected node highlighted.
+
+use std::ops::Range;
+
+use crate::views::raw::offsets::{self, Span};
+
+const COLUMNS: usize = 16;
+
+/// The bytes the hex panel last highlighted, to scroll to them once when they change.
+#[derive(Default)]
+pub struct HexView {
+    range: Option<Range<usize>>,
+}
+
+/// Draws `bytes` with those of `selected` highlighted, returning the path to the node of the byte
+/// that was clicked.
+pub fn show(
+    ui: &mut egui::Ui,
+    view: &mut HexView,
+    bytes: &[u8],
+    spans: &[Span],
+    selected: Option<u64>,
+    encoding: crate::code_page::CodePage,
+) -> Option<Vec<u64>> {
+    let span = selected.and_then(|node| spans.iter().find(|span| span.node == node));
+    let range = span.map(|span| span.range.clone());
+    let scroll = range.is_some() && range != view.range;
+    view.range = range;
+
+    ui.label(match (&view.range, selected) {
+        (Some(range), _) => format!(
+            "Bytes {:#X}..{:#X} ({} bytes)",
+            range.start,
+            range.end,
+            range.len()
+        ),
+        _ if spans.is_empty() => format!("{} bytes, not linked to the tree", bytes.len()),
+        (None, Some(_)) => format!(
+            "{} bytes, the selected node has none of its own",
+            bytes.len()
+        ),
+        (None, None) => format!("{} bytes", bytes.len()),
+    });
+    if span.is_some_and(|span| span.unlinked) {
+        ui.label(
+            "The nodes inside aren't linked to their bytes, which don't read the way lcf read them",
+        );
+    }
+    ui.separator();
+
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    let mut area = egui::ScrollArea::vertical().auto_shrink(false);
+    if scroll && let Some(range) = &view.range {
+        let row = (range.start / COLUMNS) as f32;
+        area = area.vertical_scroll_offset(row * (row_height + ui.spacing().item_spacing.y));
+    }
+
+    let mut clicked = None;
+    area.show_rows(ui, row_height, bytes.len().div_ceil(COLUMNS), |ui, rows| {
+        for row in rows {
+            let start = row * COLUMNS;
+            let line = &bytes[start..(start + COLUMNS).min(bytes.len())];
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 4.0;
+                ui.label(
+                    egui::RichText::new(format!("{start:08X}"))
+                        .monospace()
+                        .weak(),
+                );
+                for column in 0..COLUMNS {
+                    let offset = start + column;
+                    let Some(byte) = line.get(column) else {
+                        // Keeps the text of the last row in line with the rows above.
+                        ui.label(egui::RichText::new("  ").monospace());
+                        continue;
+                    };
+                    let mut text = egui::RichText::new(format!("{byte:02X}")).monospace();
+                    if view
+                        .range
+                        .as_ref()
+                        .is_some_and(|range| range.contains(&offset))
+                    {
+                        text = text
+                            .background_color(ui.visuals().selection.bg_fill)
+                            .color(ui.visuals().selection.stroke.color);
+                    }
+                    if ui
+                        .add(egui::Label::new(text).sense(egui::Sense::click()))
+                        .clicked()
+                    {
+                        clicked = Some(offset);
+                    }
+                }
+                ui.separator();
+                ui.label(egui::RichText::new(text(line, encoding)).monospace());
+            });
+        }
+    });
+
+    let index = offsets::find(spans, clicked?)?;
+    // The clicked byte is already in view, so don't scroll to the start of its node.
+    view.range = Some(spans[index].range.clone());
+    Some(offsets::path(spans, index))
+}
+
+/// One row as text. Characters spanning two rows show up as replacement characters on both.
+fn text(line: &[u8], encoding: crate::code_page::CodePage) -> String {
+    encoding
+        .to_encoding()
+        .decode(line)
+        .0
+        .chars()
+        .map(|char| if char.is_control() { '.' } else { char })
+        .collect()
+}
@@ -0,0 +1,570 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use lcf::lmu::{Panorama, PanoramaOptions, event::Event};
+
+use image::RgbaImage;
+
+use crate::{
+    assets::Assets,
+    export::Options,
+    render::{Frame, Layers},
+    save::{Character, Position},
+    sheet,
+    views::chipset::{Overlay, draw_overlay},
+};
+
+/// Settings of the map window, and the last map it rendered.
+pub struct MapView {
+    pub layers: Layers,
+    pub events: bool,
+    pub grid: bool,
+    pub panorama: bool,
+    pub playing: bool,
+    frame: Frame,
+    pub overlay: Overlay,
+    pub zoom: f32,
+    pub markers: Option<Markers>,
+    /// Why the last PNG export failed, until the app shows it.
+    pub error: Option<String>,
+    cache: Option<Cache>,
+}
+
+impl MapView {
+    /// Renders the map again on the next frame, after it changed on disk.
+    pub fn invalidate(&mut self) {
+        self.cache = None;
+    }
+
+    /// Exports draw what the window shows.
+    pub fn export_options(&self) -> Options {
+        Options {
+            layers: self.layers,
+            events: self.events,
+            grid: self.grid,
+            passability: self.overlay == Overlay::Passability,
+        }
+    }
+}
+
+impl Default for MapView {
+    fn default() -> Self {
+        Self {
+            layers: Layers::default(),
+            events: true,
+            grid: false,
+            panorama: true,
+            playing: true,
+            frame: Frame::default(),
+            overlay: Overlay::None,
+            zoom: 2.0,
+            markers: None,
+            error: None,
+            cache: None,
+        }
+    }
+}
+
+/// Cells of a side of the blocks the static tiles of a map are rendered in.
+const BLOCK: u32 = 64;
+
+/// Animated tiles per row of an animation frame's atlas.
+const ATLAS_COLUMNS: usize = 64;
+
+/// Textures of the last map shown. Tiles that never change are rendered once, in blocks as they
+/// come into view, and the animated ones once per animation frame, to be drawn underneath.
+struct Cache {
+    key: (PathBuf, u32, Layers),
+    blocks: HashMap<(u32, u32), egui::TextureHandle>,
+    /// The animated lower layer tiles the map uses, in the order of the atlases.
+    tiles: Vec<u16>,
+    /// Each of `tiles` drawn with one animation frame.
+    atlases: HashMap<Frame, egui::TextureHandle>,
+}
+
+impl Cache {
+    fn new(key: (PathBuf, u32, Layers), map_unit: &lcf::lmu::LcfMapUnit) -> Self {
+        let mut tiles = Vec::new();
+        if key.2.lower {
+            tiles.extend(
+                map_unit
+                    .lower
+                    .iter()
+                    .copied()
+                    .filter(|&tile| crate::render::animates(tile)),
+            );
+        }
+        tiles.sort_unstable();
+        tiles.dedup();
+        Self {
+            key,
+            blocks: HashMap::new(),
+            tiles,
+            atlases: HashMap::new(),
+        }
+    }
+}
+
+/// The party and vehicles of a save, marked on the map they stand on.
+pub struct Markers {
+    pub path: PathBuf,
+    pub positions: Vec<(Character, Position)>,
+    /// Scrolls the party into view the next time the map is drawn.
+    pub reveal: bool,
+}
+
+/// Draws a map unit with its events on top, returning the id of the event that was clicked.
+///
+/// `path` identifies the map, so it is only rendered again when it or the settings change.
+pub fn show(
+    ui: &mut egui::Ui,
+    view: &mut MapView,
+    path: &Path,
+    map_unit: &lcf::lmu::LcfMapUnit,
+    project: Option<&crate::project::Project>,
+    assets: &mut Assets,
+    encoding: crate::code_page::CodePage,
+) -> Option<u32> {
+    let chipset = project.and_then(|project| project.chipset_file(map_unit.chipset));
+    let tables = project
+        .and_then(|project| project.chipset(map_unit.chipset))
+        .unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut view.layers.lower, "Lower");
+        ui.checkbox(&mut view.layers.upper, "Upper");
+        ui.checkbox(&mut view.events, "Events");
+        ui.checkbox(&mut view.grid, "Grid");
+        ui.checkbox(&mut view.panorama, "Panorama");
+        super::chipset::playback(ui, &mut view.playing, &mut view.frame, &tables);
+        view.overlay.combo_box(ui, "map overlay");
+        ui.add(egui::Slider::new(&mut view.zoom, 1.0..=4.0).text("Zoom"));
+
+        if ui
+            .add_enabled(project.is_some(), egui::Button::new("Export PNG"))
+            .clicked()
+            && let Some(project) = project
+            && let Some(target) = rfd::FileDialog::new()
+                .add_filter("PNG image", &["png"])
+                .set_file_name(
+                    path.with_extension("png")
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                )
+                .save_file()
+            && let Err(err) = crate::export::export(
+                project,
+                map_unit,
+                &target,
+                assets,
+                view.export_options(),
+                encoding,
+            )
+        {
+            view.error = Some(format!("Failed to export map: {err}"));
+        }
+    });
+
+    let Some(image) = chipset.and_then(|file| assets.image("ChipSet", file, encoding)) else {
+        ui.label("Chipset not found");
+        return None;
+    };
+
+    let key = (path.to_owned(), map_unit.chipset, view.layers);
+    if view.cache.as_ref().is_none_or(|cache| cache.key != key) {
+        view.cache = Some(Cache::new(key, map_unit));
+    }
+    let Some(cache) = &mut view.cache else {
+        return None;
+    };
+
+    let mut clicked = None;
+    egui::ScrollArea::both().show(ui, |ui| {
+        let size = sheet::TILE as f32 * view.zoom;
+        let (rect, _) = ui.allocate_exact_size(
+            egui::vec2(map_unit.width as f32, map_unit.height as f32) * size,
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        if view.panorama {
+            draw_panorama(
+                ui,
+                &painter,
+                rect,
+                view.zoom,
+                &map_unit.panorama,
+                assets,
+                encoding,
+            );
+        }
+
+        // Only the visible cells, as large maps have hundreds of thousands of them.
+        let visible = ui.clip_rect().intersect(rect);
+        let first = ((visible.min - rect.min) / size).floor();
+        let last = ((visible.max - rect.min) / size).ceil();
+        let columns = first.x as u32..(last.x as u32).min(map_unit.width);
+        let rows = first.y as u32..(last.y as u32).min(map_unit.height);
+        let cell = |x: u32, y: u32| {
+            egui::Rect::from_min_size(
+                rect.min + egui::vec2(x as f32, y as f32) * size,
+                egui::Vec2::splat(size),
+            )
+        };
+
+        if !cache.tiles.is_empty() {
+            let atlas = cache.atlases.entry(view.frame).or_insert_with(|| {
+                let atlas = atlas(&image, &cache.tiles, view.frame);
+                load_texture(ui.ctx(), "map atlas", &atlas)
+            });
+            for y in rows.clone() {
+                for x in columns.clone() {
+                    let Some(index) = map_unit
+                        .lower
+                        .get((y * map_unit.width + x) as usize)
+                        .and_then(|tile| cache.tiles.binary_search(tile).ok())
+                    else {
+                        continue;
+                    };
+                    let source = egui::pos2(
+                        (index % ATLAS_COLUMNS) as f32,
+                        (index / ATLAS_COLUMNS) as f32,
+                    ) * sheet::TILE as f32;
+                    let source =
+                        egui::Rect::from_min_size(source, egui::Vec2::splat(sheet::TILE as f32));
+                    painter.image(
+                        atlas.id(),
+                        cell(x, y),
+                        super::sprite::uv(atlas, source),
+                        egui::Color32::WHITE,
+                    );
+                }
+            }
+        }
+
+        // Blocks out of view are dropped, so scrolling over a large map doesn't keep all of it.
+        let blocks = (columns.start / BLOCK..columns.end.div_ceil(BLOCK))
+            .flat_map(|x| (rows.start / BLOCK..rows.end.div_ceil(BLOCK)).map(move |y| (x, y)))
+            .collect::<Vec<_>>();
+        cache.blocks.retain(|block, _| blocks.contains(block));
+        for (x, y) in blocks {
+            let texture = cache.blocks.entry((x, y)).or_insert_with(|| {
+                let rendered = crate::render::render_area(
+                    map_unit,
+                    &image,
+                    view.layers,
+                    None,
+                    x * BLOCK..((x + 1) * BLOCK).min(map_unit.width),
+                    y * BLOCK..((y + 1) * BLOCK).min(map_unit.height),
+                );
+                load_texture(ui.ctx(), "map block", &rendered)
+            });
+            painter.image(
+                texture.id(),
+                egui::Rect::from_min_size(
+                    cell(x * BLOCK, y * BLOCK).min,
+                    texture.size_vec2() * view.zoom,
+                ),
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+
+        if view.overlay != Overlay::None {
+            for y in rows.clone() {
+                for x in columns.clone() {
+                    let index = (y * map_unit.width + x) as usize;
+                    let (Some(&lower), Some(&upper)) =
+                        (map_unit.lower.get(index), map_unit.upper.get(index))
+                    else {
+                        continue;
+                    };
+                    draw_overlay(
+                        &painter,
+                        cell(x, y),
+                        view.overlay,
+                        tables.cell_passability(lower, upper),
+                        tables.terrain(lower),
+                    );
+                }
+            }
+        }
+
+        if view.grid {
+            let stroke = egui::Stroke::new(1.0, egui::Color32::from_black_alpha(96));
+            for x in 1..map_unit.width {
+                let x = rect.min.x + x as f32 * size;
+                painter.vline(x, rect.y_range(), stroke);
+            }
+            for y in 1..map_unit.height {
+                let y = rect.min.y + y as f32 * size;
+                painter.hline(rect.x_range(), y, stroke);
+            }
+        }
+
+        if view.events {
+            for event in &map_unit.events {
+                let tile = egui::Rect::from_min_size(
+                    rect.min + egui::vec2(event.x as f32, event.y as f32) * size,
+                    egui::Vec2::splat(size),
+                );
+                draw_event(&painter, tile, view.zoom, event, chipset, assets, encoding);
+
+                let response = ui
+                    .interact(
+                        tile,
+                        ui.id().with(("event", event.id)),
+                        egui::Sense::click(),
+                    )
+                    .on_hover_text(tooltip(event, encoding));
+                if response.clicked() {
+                    clicked = Some(event.id);
+                }
+            }
+        }
+
+        if let Some(markers) = &mut view.markers
+            && markers.path == path
+        {
+            draw_markers(ui, &painter, rect, view.zoom, markers);
+        }
+    });
+    clicked
+}
+
+/// Draws `tiles` with one animation frame, `ATLAS_COLUMNS` to a row.
+fn atlas(chipset: &RgbaImage, tiles: &[u16], frame: Frame) -> RgbaImage {
+    let columns = tiles.len().min(ATLAS_COLUMNS) as u32;
+    let rows = tiles.len().div_ceil(ATLAS_COLUMNS) as u32;
+    let mut target = RgbaImage::new(columns * sheet::TILE, rows * sheet::TILE);
+    for (index, &tile) in tiles.iter().enumerate() {
+        let x = (index % ATLAS_COLUMNS) as u32 * sheet::TILE;
+        let y = (index / ATLAS_COLUMNS) as u32 * sheet::TILE;
+        crate::render::draw_tile(&mut target, chipset, tile, x, y, frame);
+    }
+    target
+}
+
+fn load_texture(ctx: &egui::Context, name: &str, image: &RgbaImage) -> egui::TextureHandle {
+    ctx.load_texture(
+        name,
+        egui::ColorImage::from_rgba_unmultiplied(
+            [image.width() as usize, image.height() as usize],
+            image.as_raw(),
+        ),
+        egui::TextureOptions::NEAREST,
+    )
+}
+
+/// Tiles the panorama behind the map, moving it along the axes that scroll automatically.
+fn draw_panorama(
+    ui: &egui::Ui,
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    zoom: f32,
+    panorama: &Panorama,
+    assets: &mut Assets,
+    encoding: crate::code_page::CodePage,
+) {
+    let Some(file) = panorama.file.as_deref().filter(|_| panorama.enabled) else {
+        return;
+    };
+    let Some(texture) = assets.texture(ui.ctx(), "Panorama", file, encoding) else {
+        return;
+    };
+    let size = texture.size_vec2() * zoom;
+    let time = ui.input(|input| input.time) as f32;
+
+    // Looping axes repeat the image across the whole map and may scroll; the others show it once.
+    let axis = |options: &PanoramaOptions, length: f32| match *options {
+        PanoramaOptions::NoLoop => None,
+        PanoramaOptions::NoAutoscroll => Some(0.0),
+        PanoramaOptions::Autoscroll(speed) => {
+            Some((-scroll_speed(speed) * zoom * time).rem_euclid(length))
+        }
+    };
+    let horizontal = axis(&panorama.horizontal, size.x);
+    let vertical = axis(&panorama.vertical, size.y);
+    let autoscroll = [&panorama.horizontal, &panorama.vertical]
+        .iter()
+        .any(|options| matches!(options, PanoramaOptions::Autoscroll(speed) if *speed != 0));
+    if autoscroll {
+        ui.ctx().request_repaint();
+    }
+
+    let visible = ui.clip_rect().intersect(rect);
+    let positions = |offset: Option<f32>, start: f32, min: f32, max: f32, length: f32| {
+        let Some(offset) = offset else {
+            return vec![start];
+        };
+        let first = start + offset + ((min - start - offset) / length).floor() * length;
+        std::iter::successors(Some(first), |x| Some(x + length))
+            .take_while(|x| *x < max)
+            .collect()
+    };
+    let xs = positions(horizontal, rect.min.x, visible.min.x, visible.max.x, size.x);
+    let ys = positions(vertical, rect.min.y, visible.min.y, visible.max.y, size.y);
+    for y in &ys {
+        for x in &xs {
+            painter.image(
+                texture.id(),
+                egui::Rect::from_min_size(egui::pos2(*x, *y), size),
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+}
+
+/// Pixels per second an autoscrolling panorama moves. The engine moves it by 2^|speed|
+/// sixteenths of a pixel every frame, at 60 frames per second.
+fn scroll_speed(speed: i32) -> f32 {
+    speed.signum() as f32 * 2f32.powi(speed.abs()) / 16.0 * 60.0
+}
+
+/// Draws the graphic of an event's first page, or a marker when it has none.
+fn draw_event(
+    painter: &egui::Painter,
+    tile: egui::Rect,
+    zoom: f32,
+    event: &Event,
+    chipset: Option<&[u8]>,
+    assets: &mut Assets,
+    encoding: crate::code_page::CodePage,
+) {
+    // The first upper layer tile is always blank, so a page using it has no graphic.
+    if let Some(graphic) = event.pages.first().map(|page| &page.graphic)
+        && !(graphic.file.is_empty() && graphic.index == 0)
+    {
+        let tint = if graphic.transparent {
+            egui::Color32::from_white_alpha(128)
+        } else {
+            egui::Color32::WHITE
+        };
+
+        // Characters are centred horizontally on their tile and stand on its bottom edge.
+        let sprite = if graphic.file.is_empty() {
+            chipset
+                .and_then(|file| assets.texture(painter.ctx(), "ChipSet", file, encoding))
+                .map(|texture| {
+                    let (x, y) = sheet::upper_tile(graphic.index);
+                    let size = egui::Vec2::splat(sheet::TILE as f32);
+                    (texture, egui::pos2(x as f32, y as f32), size, tile.min)
+                })
+        } else {
+            assets
+                .texture(painter.ctx(), "CharSet", &graphic.file, encoding)
+                .map(|texture| {
+                    let (x, y) = sheet::charset_frame(
+                        graphic.index,
+                        graphic.direction as u32,
+                        graphic.pattern,
+                    );
+                    let size = egui::vec2(sheet::FRAME_WIDTH as f32, sheet::FRAME_HEIGHT as f32);
+                    let offset = egui::vec2(
+                        (sheet::TILE as f32 - size.x) / 2.0,
+                        sheet::TILE as f32 - size.y,
+                    );
+                    (
+                        texture,
+                        egui::pos2(x as f32, y as f32),
+                        size,
+                        tile.min + offset * zoom,
+                    )
+                })
+        };
+
+        if let Some((texture, source, size, position)) = sprite {
+            painter.image(
+                texture.id(),
+                egui::Rect::from_min_size(position, size * zoom),
+                super::sprite::uv(&texture, egui::Rect::from_min_size(source, size)),
+                tint,
+            );
+            return;
+        }
+    }
+
+    painter.rect(
+        tile.shrink(zoom),
+        2.0,
+        egui::Color32::from_rgba_unmultiplied(255, 200, 0, 64),
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 200, 0)),
+        egui::StrokeKind::Inside,
+    );
+}
+
+/// Outlines the tile of each character with an arrow in the direction it moves.
+fn draw_markers(
+    ui: &egui::Ui,
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    zoom: f32,
+    markers: &mut Markers,
+) {
+    let size = sheet::TILE as f32 * zoom;
+    for (character, position) in &markers.positions {
+        let tile = egui::Rect::from_min_size(
+            rect.min + egui::vec2(position.x as f32, position.y as f32) * size,
+            egui::Vec2::splat(size),
+        );
+        let color = if *character == Character::Party {
+            egui::Color32::from_rgb(0, 200, 255)
+        } else {
+            egui::Color32::from_rgb(255, 80, 200)
+        };
+        let stroke = egui::Stroke::new(2.0, color);
+        painter.rect_stroke(tile, 2.0, stroke, egui::StrokeKind::Outside);
+        let (direction, name) = match position.direction {
+            0 => (egui::vec2(0.0, -1.0), "up"),
+            1 => (egui::vec2(1.0, 0.0), "right"),
+            3 => (egui::vec2(-1.0, 0.0), "left"),
+            _ => (egui::vec2(0.0, 1.0), "down"),
+        };
+        painter.arrow(
+            tile.center() - direction * size * 0.3,
+            direction * size * 0.6,
+            stroke,
+        );
+        painter.text(
+            tile.center_top(),
+            egui::Align2::CENTER_BOTTOM,
+            character.to_str(),
+            egui::FontId::proportional(12.0),
+            color,
+        );
+
+        ui.interact(
+            tile,
+            ui.id().with(("marker", character.to_str())),
+            egui::Sense::hover(),
+        )
+        .on_hover_text(format!(
+            "{} at ({}, {}), moving {name}",
+            character.to_str(),
+            position.x,
+            position.y
+        ));
+        if markers.reveal && *character == Character::Party {
+            ui.scroll_to_rect(tile, Some(egui::Align::Center));
+        }
+    }
+    markers.reveal = false;
+}
+
+fn tooltip(event: &Event, encoding: crate::code_page::CodePage) -> String {
+    let triggers = event
+        .pages
+        .iter()
+        .map(|page| crate::labels::trigger(page.trigger))
+        .collect::<Vec<_>>();
+    format!(
+        "E{:04}: {}\nPages: {}\nTriggers: {}",
+        event.id,
+        encoding.to_encoding().decode(&event.name).0,
+        event.pages.len(),
+        triggers.join(", ")
+    )
+}
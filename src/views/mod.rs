@@ -1,3 +1,10 @@
+pub mod chipset;
+pub mod commands;
 pub mod data_base;
+pub mod grid;
+pub mod hex;
+pub mod map;
 pub mod map_unit;
+pub mod move_route;
 pub mod raw;
+pub mod sprite;
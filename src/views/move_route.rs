@@ -0,0 +1,18 @@
+use crate::move_route::MoveRoute;
+
+/// Draws the flags and decoded commands of a move route under `node`.
+pub fn update(
+    route: &MoveRoute,
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+    node: u64,
+) {
+    builder.leaf(node, format!("Repeat: {}", route.repeat));
+    builder.leaf(node + 1, format!("Skippable: {}", route.skippable));
+    for (index, command) in route.commands.iter().enumerate() {
+        builder.leaf(
+            node + 2 + index as u64,
+            format!("{index}: {}", command.label(encoding)),
+        );
+    }
+}
@@ -1,99 +1,161 @@
+use egui_ltreeview::NodeBuilder;
 use lcf::{
     enums::Trigger,
-    helpers::{Array, Chunk, Number, UnknownChunk},
+    helpers::{Array, Chunk, Number},
     raw::ldb::{
         LcfDataBaseChunk, RawLcfDataBase, chipset::ChipSetChunk, common_event::CommonEventChunk,
         switch::SwitchChunk, variable::VariableChunk,
     },
 };
 
+use crate::{
+    assets::Assets,
+    chipset::{ChipSet, Passability},
+    fields::database,
+    references::Target,
+    terms,
+    views::raw::{
+        fields::{draw_bytes, draw_chunk_records, draw_fields, draw_records},
+        ids,
+    },
+};
+
 pub fn update(
     database: &RawLcfDataBase,
     builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
     encoding: crate::code_page::CodePage,
+    references: &mut Option<Target>,
+    assets: &mut Assets,
 ) {
     for (index, chunk) in database.0.inner_vec.iter().enumerate() {
         let node = index as u64;
         match &chunk.data {
-            LcfDataBaseChunk::Actors(bytes) => builder.leaf(node, format!("Actors: {bytes:?}")),
-            LcfDataBaseChunk::Skills(bytes) => builder.leaf(node, format!("Skills: {bytes:?}")),
-            LcfDataBaseChunk::Items(bytes) => builder.leaf(node, format!("Items: {bytes:?}")),
+            LcfDataBaseChunk::Actors(bytes) => {
+                draw_bytes(node, "Actors", bytes, database::ACTOR, builder, encoding)
+            }
+            LcfDataBaseChunk::Skills(bytes) => {
+                draw_bytes(node, "Skills", bytes, database::SKILL, builder, encoding)
+            }
+            LcfDataBaseChunk::Items(bytes) => {
+                draw_bytes(node, "Items", bytes, database::ITEM, builder, encoding)
+            }
             LcfDataBaseChunk::Enemies(enemies) => {
-                if builder.dir(node, "Enemies") {
-                    for (index, (_, chunks)) in enemies.inner_vec.iter().enumerate() {
-                        let node = node << 8;
-                        if builder.dir(node, index.to_string()) {
-                            draw_chunks(node + index as u64 + 1, &chunks, builder);
-                        }
-                        builder.close_dir();
-                    }
-                }
-                builder.close_dir();
+                draw_records(node, "Enemies", enemies, database::ENEMY, builder, encoding)
             }
             LcfDataBaseChunk::Troops(chunks) => {
-                if builder.dir(node, "Troops") {
-                    draw_chunks(node, &chunks, builder);
-                }
-                builder.close_dir();
+                draw_chunk_records(node, "Troops", chunks, database::TROOP, builder, encoding)
             }
-            LcfDataBaseChunk::Terrain(bytes) => builder.leaf(node, format!("Terrain: {bytes:?}")),
-            LcfDataBaseChunk::Attributes(chunks) => {
-                if builder.dir(node, "Attributes") {
-                    draw_chunks(node, &chunks, builder);
-                }
-                builder.close_dir();
+            LcfDataBaseChunk::Terrain(bytes) => {
+                draw_bytes(node, "Terrain", bytes, database::TERRAIN, builder, encoding)
             }
+            LcfDataBaseChunk::Attributes(chunks) => draw_chunk_records(
+                node,
+                "Attributes",
+                chunks,
+                database::ATTRIBUTE,
+                builder,
+                encoding,
+            ),
             LcfDataBaseChunk::States(chunks) => {
-                if builder.dir(node, "States") {
-                    draw_chunks(node, &chunks, builder);
-                }
-                builder.close_dir();
-            }
-            LcfDataBaseChunk::Animations(bytes) => {
-                builder.leaf(node, format!("Animations: {bytes:?}"))
+                draw_chunk_records(node, "States", chunks, database::STATE, builder, encoding)
             }
+            LcfDataBaseChunk::Animations(bytes) => draw_bytes(
+                node,
+                "Animations",
+                bytes,
+                database::ANIMATION,
+                builder,
+                encoding,
+            ),
             LcfDataBaseChunk::ChipSet(chipset) => {
                 if builder.dir(node, "Chipset") {
-                    update_chipset(&chipset.inner_vec, builder, encoding, node);
+                    update_chipset(&chipset.inner_vec, builder, encoding, node, assets);
                 }
                 builder.close_dir();
             }
             LcfDataBaseChunk::Terms(chunks) => {
                 if builder.dir(node, "Terms") {
-                    let node = node << 16;
-                    builder.leaf(node, format!("Null terminated: {}", chunks.null_terminated));
+                    builder.leaf(
+                        ids::terms_terminator(node),
+                        format!("Null terminated: {}", chunks.null_terminated),
+                    );
 
-                    for (index, chunk) in chunks.inner_vec.iter().enumerate() {
-                        builder.leaf(
-                            node + 1 + index as u64,
-                            format!(
-                                "{:?}: {}",
-                                chunk.id,
-                                encoding.to_encoding().decode(&chunk.bytes).0
-                            ),
-                        );
+                    let terms = chunks
+                        .inner_vec
+                        .iter()
+                        .enumerate()
+                        .map(|(index, chunk)| {
+                            let id: u32 = chunk.id.clone().into();
+                            (index, id, &chunk.bytes)
+                        })
+                        .collect::<Vec<_>>();
+                    for (category_index, category) in terms::CATEGORIES.iter().enumerate() {
+                        let terms = terms
+                            .iter()
+                            .filter(|(_, id, _)| terms::Category::of(*id) == *category)
+                            .collect::<Vec<_>>();
+                        if terms.is_empty() {
+                            continue;
+                        }
+                        let flagged = terms
+                            .iter()
+                            .filter(|(_, _, bytes)| terms::issue(bytes, encoding).is_some())
+                            .count();
+                        let label = match flagged {
+                            0 => category.to_str().to_string(),
+                            _ => format!("{} ({flagged} flagged)", category.to_str()),
+                        };
+
+                        if builder.dir(ids::term_category(node, category_index), label) {
+                            for (index, id, bytes) in terms {
+                                let role = match (terms::variant(*id), terms::role(*id)) {
+                                    (Some(variant), Some(role)) => format!("{variant}: {role}"),
+                                    (None, Some(role)) => role.to_string(),
+                                    _ => format!("Term {id}"),
+                                };
+                                let text = encoding.to_encoding().decode(bytes).0;
+                                builder.leaf(
+                                    ids::term(node, *index),
+                                    match terms::issue(bytes, encoding) {
+                                        Some(issue) => {
+                                            format!("⚠ {role}: {text} ({})", issue.to_str())
+                                        }
+                                        None => format!("{role}: {text}"),
+                                    },
+                                );
+                            }
+                        }
+                        builder.close_dir();
                     }
                 }
                 builder.close_dir();
             }
             LcfDataBaseChunk::System(chunks) => {
                 if builder.dir(node, "System") {
-                    draw_chunks(node, &chunks, builder);
+                    draw_fields(node, chunks, database::SYSTEM, builder, encoding);
                 }
                 builder.close_dir();
             }
             LcfDataBaseChunk::Switches(switches) => {
                 if builder.dir(node, "Switches") {
-                    let node = node << 16;
                     for (index, (id, chunks)) in switches.inner_vec.iter().enumerate() {
-                        let node = (node + index as u64 + 1) << 8;
-                        if builder.dir(node, id.to_string()) {
-                            builder
-                                .leaf(node, format!("Null terminated: {}", chunks.null_terminated));
+                        let node = ids::named(node, index);
+                        if builder.node(NodeBuilder::dir(node).label(id.to_string()).context_menu(
+                            |ui| {
+                                if ui.button("References").clicked() {
+                                    *references = Some(Target::Switch(id.0));
+                                    ui.close();
+                                }
+                            },
+                        )) {
+                            builder.leaf(
+                                ids::named_terminator(node),
+                                format!("Null terminated: {}", chunks.null_terminated),
+                            );
 
                             for (index, chunk) in chunks.inner_vec.iter().enumerate() {
                                 builder.leaf(
-                                    node + index as u64 + 1,
+                                    ids::named_field(node, index),
                                     match &chunk.data {
                                         SwitchChunk::Name(bytes) => {
                                             encoding.to_encoding().decode(&bytes).0.to_string()
@@ -112,16 +174,24 @@ pub fn update(
             }
             LcfDataBaseChunk::Variables(variables) => {
                 if builder.dir(node, "Variables") {
-                    let node = node << 16;
                     for (index, (id, chunks)) in variables.inner_vec.iter().enumerate() {
-                        let node = (node + index as u64 + 1) << 8;
-                        if builder.dir(node, id.to_string()) {
-                            builder
-                                .leaf(node, format!("Null terminated: {}", chunks.null_terminated));
+                        let node = ids::named(node, index);
+                        if builder.node(NodeBuilder::dir(node).label(id.to_string()).context_menu(
+                            |ui| {
+                                if ui.button("References").clicked() {
+                                    *references = Some(Target::Variable(id.0));
+                                    ui.close();
+                                }
+                            },
+                        )) {
+                            builder.leaf(
+                                ids::named_terminator(node),
+                                format!("Null terminated: {}", chunks.null_terminated),
+                            );
 
                             for (index, chunk) in chunks.inner_vec.iter().enumerate() {
                                 builder.leaf(
-                                    node + index as u64 + 1,
+                                    ids::named_field(node, index),
                                     match &chunk.data {
                                         VariableChunk::Name(bytes) => {
                                             encoding.to_encoding().decode(&bytes).0.to_string()
@@ -140,17 +210,16 @@ pub fn update(
             }
             LcfDataBaseChunk::CommonEvents(events) => {
                 if builder.dir(node, "Common Events") {
-                    let node = node << 16;
                     for (index, (id, chunks)) in events.inner_vec.iter().enumerate() {
-                        let node = (node + index as u64 + 1) << 8;
+                        let node = ids::named(node, index);
                         if builder.dir(node, id.to_string()) {
                             builder.leaf(
-                                node + 1,
+                                ids::named_terminator(node),
                                 format!("Null terminated: {}", chunks.null_terminated),
                             );
 
                             for (index, chunk) in chunks.inner_vec.iter().enumerate() {
-                                let node = node + index as u64 + 2;
+                                let node = ids::named_field(node, index);
                                 builder.leaf(
                                     node,
                                     match &chunk.data {
@@ -161,16 +230,8 @@ pub fn update(
                                             "Trigger: {}",
                                             Trigger::try_from(val.0).map_or_else(
                                                 |_| val.0.to_string(),
-                                                |repr| {
-                                                    match repr {
-                                                        Trigger::ActionButton => "Action Button",
-                                                        Trigger::PlayerTouch => "Player Touch",
-                                                        Trigger::EventTouch => "Event Touch",
-                                                        Trigger::Autorun => "Autorun",
-                                                        Trigger::Parallel => "Parallel process",
-                                                    }
-                                                    .to_string()
-                                                },
+                                                |trigger| crate::labels::trigger(trigger)
+                                                    .to_string(),
                                             )
                                         ),
                                         CommonEventChunk::SwitchState(val) => {
@@ -186,7 +247,7 @@ pub fn update(
                                             builder.dir(node, "Commands");
                                             for (index, command) in commands.0.iter().enumerate() {
                                                 builder.leaf(
-                                                    (node << 8) + index as u64,
+                                                    ids::command(node, index),
                                                     format!(
                                                         "{index}: {}{:?} {}",
                                                         "\t".repeat(command.indent as usize),
@@ -223,51 +284,127 @@ pub fn update(
     }
 }
 
+/// Node ids leading to a common event, or one of its commands, as laid out by [`update`].
+pub fn common_event_path(
+    database: &RawLcfDataBase,
+    id: u32,
+    command: Option<usize>,
+) -> Option<Vec<u64>> {
+    let (index, events) = database
+        .0
+        .inner_vec
+        .iter()
+        .enumerate()
+        .find_map(|(index, chunk)| match &chunk.data {
+            LcfDataBaseChunk::CommonEvents(events) => Some((index, events)),
+            _ => None,
+        })?;
+    let node = index as u64;
+    let (event, (_, chunks)) = events
+        .inner_vec
+        .iter()
+        .enumerate()
+        .find(|(_, (event, _))| event.0 == id)?;
+    let event = ids::named(node, event);
+
+    let mut path = vec![node, event];
+    if let Some(command) = command {
+        let commands = chunks
+            .inner_vec
+            .iter()
+            .position(|chunk| matches!(chunk.data, CommonEventChunk::Commands(_)))?;
+        let commands = ids::named_field(event, commands);
+        path.extend([commands, ids::command(commands, command)]);
+    }
+    Some(path)
+}
+
 fn update_chipset(
     chipsets: &[(Number, Array<Chunk<ChipSetChunk>>)],
     builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
     encoding: crate::code_page::CodePage,
     node: u64,
+    assets: &mut Assets,
 ) {
-    let node = node << 16;
     for (index, (id, chunks)) in chipsets.iter().enumerate() {
-        let node = node + index as u64;
-        builder.dir(node, format!("ChipSet {}", id.0));
+        let chipset = ids::chipset(node, index);
+        builder.dir(chipset, format!("ChipSet {}", id.0));
 
-        let node = node << 8;
         for (index, field) in chunks.inner_vec.iter().enumerate() {
-            let label = match &field.data {
+            let node = ids::field(chipset, index);
+            let (name, items) = match &field.data {
                 ChipSetChunk::Name(bytes) => {
-                    format!("Name: {}", encoding.to_encoding().decode(bytes).0)
+                    builder.leaf(
+                        node,
+                        format!("Name: {}", encoding.to_encoding().decode(bytes).0),
+                    );
+                    continue;
                 }
                 ChipSetChunk::File(bytes) => {
-                    format!("File: {}", encoding.to_encoding().decode(bytes).0)
+                    builder.leaf(
+                        node,
+                        format!("File: {}", encoding.to_encoding().decode(bytes).0),
+                    );
+                    continue;
+                }
+                ChipSetChunk::Terrain(items) => (
+                    "Terrain",
+                    items.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                ),
+                ChipSetChunk::PassabilityLower(items) => (
+                    "Passable (Lower)",
+                    items
+                        .iter()
+                        .map(|val| Passability(val.0 as u8).label())
+                        .collect(),
+                ),
+                ChipSetChunk::PassabilityUpper(items) => (
+                    "Passable (Upper)",
+                    items
+                        .iter()
+                        .map(|val| Passability(val.0 as u8).label())
+                        .collect(),
+                ),
+                ChipSetChunk::AnimationType(val) => {
+                    builder.leaf(node, format!("Animation Type: {}", val.0));
+                    continue;
+                }
+                ChipSetChunk::AnimationSpeed(val) => {
+                    builder.leaf(node, format!("Animation Speed: {}", val.0));
+                    continue;
                 }
-                ChipSetChunk::Terrain(items) => format!("Terrain: {items:?}"),
-                ChipSetChunk::PassabilityLower(items) => format!("Passable (Lower): {items:?}"),
-                ChipSetChunk::PassabilityUpper(items) => format!("Passable (Upper): {items:?}"),
-                ChipSetChunk::AnimationType(val) => format!("Animation Type: {}", val.0),
-                ChipSetChunk::AnimationSpeed(val) => format!("Animation Speed: {}", val.0),
                 ChipSetChunk::Unknown { id, bytes } => {
-                    format!("Field {}: {:?}", id, bytes)
+                    builder.leaf(node, format!("Field {}: {:?}", id, bytes));
+                    continue;
                 }
             };
-            builder.leaf(node + index as u64, label);
-        }
-        builder.close_dir();
-    }
-}
 
-fn draw_chunks(
-    node: u64,
-    chunks: &Array<Chunk<UnknownChunk>>,
-    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
-) {
-    let node = node << 16;
-    builder.leaf(node, format!("Null terminated: {}", chunks.null_terminated));
+            if builder.dir(node, name) {
+                for (index, item) in items.iter().enumerate() {
+                    builder.leaf(ids::value(node, index), format!("Tile {index}: {item}"));
+                }
+            }
+            builder.close_dir();
+        }
 
-    for (index, chunk) in chunks.inner_vec.iter().enumerate() {
-        let UnknownChunk::Unknown { bytes, id } = &chunk.data;
-        builder.leaf(node + 1 + index as u64, format!("Field {id}: {bytes:?}"));
+        let file = chunks.inner_vec.iter().find_map(|chunk| match &chunk.data {
+            ChipSetChunk::File(bytes) => Some(bytes),
+            _ => None,
+        });
+        if let Some(file) = file {
+            let tables = ChipSet::from_chunks(chunks);
+            let node = ids::field(chipset, chunks.inner_vec.len());
+            builder.node(NodeBuilder::leaf(node).height(920.0).label_ui(|ui| {
+                crate::views::chipset::preview(
+                    ui,
+                    assets,
+                    file,
+                    &tables,
+                    encoding,
+                    egui::Id::new(("chipset", node)),
+                )
+            }));
+        }
+        builder.close_dir();
     }
 }
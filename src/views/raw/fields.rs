@@ -0,0 +1,121 @@
+use lcf::helpers::{Array, Array2D, Chunk, UnknownChunk};
+
+use crate::{
+    fields::{Field, Kind},
+    views::raw::ids::child,
+};
+
+/// Draws a chunk lcf keeps as bytes as a list of records, or as the bytes when they don't parse.
+pub fn draw_bytes(
+    node: u64,
+    name: &str,
+    bytes: &[u8],
+    fields: &'static [Field],
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+) {
+    match crate::fields::records(bytes) {
+        Some(records) => draw_records(node, name, &records, fields, builder, encoding),
+        None => builder.leaf(node, format!("{name}: {bytes:?}")),
+    }
+}
+
+/// Draws a chunk lcf keeps as bytes as one record, or as the bytes when they don't parse.
+pub fn draw_struct(
+    node: u64,
+    name: &str,
+    bytes: &[u8],
+    fields: &'static [Field],
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+) {
+    match crate::fields::chunks(bytes) {
+        Some(chunks) => {
+            if builder.dir(node, name) {
+                draw_fields(node, &chunks, fields, builder, encoding);
+            }
+            builder.close_dir();
+        }
+        None => builder.leaf(node, format!("{name}: {bytes:?}")),
+    }
+}
+
+/// Draws a chunk lcf reads as one list of chunks, though it holds records, one record at a time.
+/// Falls back to the chunks lcf read when the bytes they were read from don't parse as records.
+pub fn draw_chunk_records(
+    node: u64,
+    name: &str,
+    chunks: &Array<Chunk<UnknownChunk>>,
+    fields: &'static [Field],
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+) {
+    match crate::fields::records(&crate::fields::encode_chunks(chunks)) {
+        Some(records) => draw_records(node, name, &records, fields, builder, encoding),
+        None => {
+            if builder.dir(node, name) {
+                draw_fields(node, chunks, fields, builder, encoding);
+            }
+            builder.close_dir();
+        }
+    }
+}
+
+pub fn draw_records(
+    node: u64,
+    name: &str,
+    records: &Array2D<UnknownChunk>,
+    fields: &'static [Field],
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+) {
+    if builder.dir(node, name) {
+        for (index, (id, chunks)) in records.inner_vec.iter().enumerate() {
+            let node = child(node, index);
+            let label = match crate::fields::record_name(chunks, fields, encoding) {
+                Some(name) => format!("{}: {name}", id.0),
+                None => id.0.to_string(),
+            };
+            if builder.dir(node, label) {
+                draw_fields(node, chunks, fields, builder, encoding);
+            }
+            builder.close_dir();
+        }
+    }
+    builder.close_dir();
+}
+
+/// Draws the chunks of one record, named and decoded where `fields` knows them.
+pub fn draw_fields(
+    node: u64,
+    chunks: &Array<Chunk<UnknownChunk>>,
+    fields: &'static [Field],
+    builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
+    encoding: crate::code_page::CodePage,
+) {
+    builder.leaf(
+        child(node, 0),
+        format!("Null terminated: {}", chunks.null_terminated),
+    );
+
+    for (index, chunk) in chunks.inner_vec.iter().enumerate() {
+        let node = child(node, index + 1);
+        let UnknownChunk::Unknown { id, bytes } = &chunk.data;
+        let Some(field) = crate::fields::find(fields, *id) else {
+            builder.leaf(node, format!("Field {id}: {bytes:?}"));
+            continue;
+        };
+        match field.kind {
+            Kind::Records(fields) => {
+                draw_bytes(node, field.name, bytes, fields, builder, encoding);
+            }
+            Kind::Struct(fields) => {
+                draw_struct(node, field.name, bytes, fields, builder, encoding);
+            }
+            kind => match crate::fields::value(kind, bytes, encoding) {
+                Some(value) => builder.leaf(node, format!("{}: {value}", field.name)),
+                None => builder.leaf(node, format!("{}: {bytes:?}", field.name)),
+            },
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Node ids of the raw views. The views number their nodes with these, and [`super::offsets`]
+//! numbers the bytes it walks with them too, so the hex view links to the nodes the tree shows.
+//!
+//! Top level chunks are numbered by their index in the file.
+
+/// The `index`th child of `node`. Records nest deeper than the shifted ids below have room for,
+/// so these are hashed instead.
+pub fn child(node: u64, index: usize) -> u64 {
+    egui::Id::new((node, index)).value()
+}
+
+/// A chunk inside `node`: of a chipset, of the system of a save, of a map in the map tree, or of
+/// the condition or move route of an event page.
+pub fn field(node: u64, index: usize) -> u64 {
+    (node << 8) + index as u64
+}
+
+/// An entry of a list of numbers, like the switches of a save or the tiles of a chipset table.
+pub fn value(list: u64, index: usize) -> u64 {
+    (list << 16) + index as u64
+}
+
+/// An event command, or a command of a move route.
+pub fn command(commands: u64, index: usize) -> u64 {
+    (commands << 8) + index as u64
+}
+
+/// A chipset of the database.
+pub fn chipset(chunk: u64, index: usize) -> u64 {
+    (chunk << 16) + index as u64
+}
+
+/// Whether the terms of the database are null terminated.
+pub fn terms_terminator(chunk: u64) -> u64 {
+    chunk << 16
+}
+
+/// A term of the database, numbered by its place in the chunk whatever category it is filed in.
+pub fn term(chunk: u64, index: usize) -> u64 {
+    (chunk << 16) + 1 + index as u64
+}
+
+/// A category of terms, which has no bytes of its own.
+pub fn term_category(chunk: u64, category: usize) -> u64 {
+    (chunk << 16) + 0x8000 + category as u64
+}
+
+/// A switch, variable or common event of the database.
+pub fn named(chunk: u64, index: usize) -> u64 {
+    ((chunk << 16) + index as u64 + 1) << 8
+}
+
+/// Whether the chunks of a switch, variable or common event are null terminated.
+pub fn named_terminator(record: u64) -> u64 {
+    record + 1
+}
+
+/// A chunk of a switch, variable or common event.
+pub fn named_field(record: u64, index: usize) -> u64 {
+    record + 2 + index as u64
+}
+
+/// The map the map tree has selected.
+pub const ACTIVE: u64 = 0;
+
+/// The order of the maps in the map tree.
+pub const ORDER: u64 = 1;
+
+pub fn order_entry(index: usize) -> u64 {
+    2 + index as u64
+}
+
+/// Where the game starts.
+pub const START: u64 = 1 << 16;
+
+pub fn start_field(index: usize) -> u64 {
+    START + 1 + index as u64
+}
+
+/// The maps of the map tree.
+pub const MAPS: u64 = 2 << 16;
+
+pub fn map(index: usize) -> u64 {
+    MAPS + 1 + index as u64
+}
+
+/// One of the four bounds of the area of a map.
+pub fn area_bound(area: u64, index: usize) -> u64 {
+    (area << 2) + index as u64
+}
+
+/// A chunk of the music of a map.
+pub fn bgm_field(bgm: u64, index: usize) -> u64 {
+    (bgm << 4) + index as u64
+}
+
+/// An event of a map.
+pub fn event(chunk: u64, index: usize) -> u64 {
+    (chunk << 4) + index as u64
+}
+
+/// A chunk of an event.
+pub fn event_field(event: u64, index: usize) -> u64 {
+    (event << 14) + index as u64
+}
+
+/// A page of an event.
+pub fn page(pages: u64, index: usize) -> u64 {
+    (pages << 7) + index as u64
+}
+
+/// A chunk of an event page.
+pub fn page_field(page: u64, index: usize) -> u64 {
+    (page << 8) + index as u64
+}
@@ -3,20 +3,21 @@ use lcf::{
     raw::lmt::{RawLcfMapTree, bgm::MapBGMChunk, map::MapChunk, start::StartChunk},
 };
 
+use crate::views::raw::ids;
+
 pub fn update(
     map_tree: &RawLcfMapTree,
     builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
     encoding: crate::code_page::CodePage,
 ) {
-    builder.leaf(0, format!("Active: {}", map_tree.active.0));
-    builder.dir(1, "Order");
+    builder.leaf(ids::ACTIVE, format!("Active: {}", map_tree.active.0));
+    builder.dir(ids::ORDER, "Order");
     for (index, id) in map_tree.order.iter().enumerate() {
-        builder.leaf(2 + index as u64, format!("{index}: {}", id.0));
+        builder.leaf(ids::order_entry(index), format!("{index}: {}", id.0));
     }
     builder.close_dir();
 
-    let node = 1 << 16;
-    builder.dir(node, "Start");
+    builder.dir(ids::START, "Start");
     for (index, chunk) in map_tree.start.inner_vec.iter().enumerate() {
         let label = match &chunk.data {
             StartChunk::PartyMapID(val) => format!("Party Map ID: {}", val.0),
@@ -35,7 +36,7 @@ pub fn update(
                 format!("Field {}: [{:?}]", id, bytes)
             }
         };
-        builder.leaf(node + 1 + index as u64, label);
+        builder.leaf(ids::start_field(index), label);
     }
     builder.close_dir();
 
@@ -47,14 +48,12 @@ pub fn update_maps(
     builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
     encoding: crate::code_page::CodePage,
 ) {
-    let node = 2 << 16;
-    builder.dir(node, "Maps");
+    builder.dir(ids::MAPS, "Maps");
     for (index, (id, chunks)) in maps.iter().enumerate() {
-        let node = node + 1 + index as u64;
-        builder.dir(node, format!("Map {}", id.0));
-        let node = node << 8;
+        let map = ids::map(index);
+        builder.dir(map, format!("Map {}", id.0));
         for (index, chunk) in chunks.inner_vec.iter().enumerate() {
-            let node = node + index as u64;
+            let node = ids::field(map, index);
             match &chunk.data {
                 MapChunk::AreaRange {
                     begin_x,
@@ -63,16 +62,14 @@ pub fn update_maps(
                     end_y,
                 } => {
                     builder.dir(node, "Area Range");
-                    let node = node << 2;
-                    builder.leaf(node, format!("Begin X: {begin_x}"));
-                    builder.leaf(node + 1, format!("Begin Y: {begin_y}"));
-                    builder.leaf(node + 2, format!("End X: {end_x}"));
-                    builder.leaf(node + 3, format!("End Y: {end_y}"));
+                    builder.leaf(ids::area_bound(node, 0), format!("Begin X: {begin_x}"));
+                    builder.leaf(ids::area_bound(node, 1), format!("Begin Y: {begin_y}"));
+                    builder.leaf(ids::area_bound(node, 2), format!("End X: {end_x}"));
+                    builder.leaf(ids::area_bound(node, 3), format!("End Y: {end_y}"));
                     builder.close_dir();
                 }
                 MapChunk::BGMData(chunks) => {
                     builder.dir(node, "BGM Data");
-                    let node = node << 4;
                     for (index, chunk) in chunks.inner_vec.iter().enumerate() {
                         let label = match &chunk.data {
                             MapBGMChunk::FileName(bytes) => {
@@ -92,7 +89,7 @@ pub fn update_maps(
                                 format!("Field {}: {:?}", id, bytes)
                             }
                         };
-                        builder.leaf(node + index as u64, label)
+                        builder.leaf(ids::bgm_field(node, index), label)
                     }
                     builder.close_dir();
                 }
@@ -136,3 +133,24 @@ pub fn update_maps(
     }
     builder.close_dir();
 }
+
+/// Node ids leading to the parent of a map, as laid out by [`update_maps`].
+pub fn parent_path(map_tree: &RawLcfMapTree, id: u32) -> Option<Vec<u64>> {
+    let (index, (_, chunks)) = map_tree
+        .maps
+        .inner_vec
+        .iter()
+        .enumerate()
+        .find(|(_, (map, _))| map.0 == id)?;
+    let node = ids::map(index);
+
+    let mut path = vec![ids::MAPS, node];
+    if let Some(parent) = chunks
+        .inner_vec
+        .iter()
+        .position(|chunk| matches!(chunk.data, MapChunk::Parent(_)))
+    {
+        path.push(ids::field(node, parent));
+    }
+    Some(path)
+}
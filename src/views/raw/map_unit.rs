@@ -1,11 +1,16 @@
-use lcf::raw::lmu::{
-    LcfMapUnitChunk, RawLcfMapUnit,
-    event::{
-        EventChunk, condition::EventPageConditionChunk, move_route::EventMoveRouteChunk,
-        page::EventPageChunk,
+use lcf::{
+    enums::Trigger,
+    raw::lmu::{
+        LcfMapUnitChunk, RawLcfMapUnit,
+        event::{
+            EventChunk, condition::EventPageConditionChunk, move_route::EventMoveRouteChunk,
+            page::EventPageChunk,
+        },
     },
 };
 
+use crate::views::raw::ids;
+
 pub fn update(
     map_unit: &RawLcfMapUnit,
     builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
@@ -53,14 +58,12 @@ pub fn update(
             }
             LcfMapUnitChunk::Events(chunks) => {
                 builder.dir(node, "Events");
-                let node = node << 4;
                 for (index, (id, chunks)) in chunks.inner_vec.iter().enumerate() {
-                    let node = node + index as u64;
-                    builder.dir(node, format!("Event {}", id.0));
-                    let node = node << 14;
-                    for (index, event) in chunks.iter().enumerate() {
-                        let node = node + index as u64;
-                        let label = match &event.data {
+                    let event = ids::event(node, index);
+                    builder.dir(event, format!("Event {}", id.0));
+                    for (index, chunk) in chunks.iter().enumerate() {
+                        let node = ids::event_field(event, index);
+                        let label = match &chunk.data {
                             EventChunk::Name(bytes) => {
                                 format!("Name: {}", encoding.to_encoding().decode(bytes).0)
                             }
@@ -68,19 +71,18 @@ pub fn update(
                             EventChunk::PositionY(val) => format!("Y: {}", val.0),
                             EventChunk::Pages(chunks) => {
                                 builder.dir(node, "Pages");
-                                let node = node << 7;
                                 for (index, (id, chunks)) in chunks.inner_vec.iter().enumerate() {
-                                    builder.dir(node, format!("Page {}", id.0));
-                                    let node = (node << 8) + index as u64;
+                                    let page = ids::page(node, index);
+                                    builder.dir(page, format!("Page {}", id.0));
                                     for (index, chunk) in chunks.inner_vec.iter().enumerate() {
-                                        let node = node + index as u64;
+                                        let node = ids::page_field(page, index);
                                         let label = match &chunk.data {
                                             EventPageChunk::Condition(chunks) => {
                                                 builder.dir(node, "Condition");
                                                 for (index, chunk) in
                                                     chunks.inner_vec.iter().enumerate()
                                                 {
-                                                    let node = (node << 8) + index as u64;
+                                                    let node = ids::field(node, index);
                                                     let label = match &chunk.data {
                                                         EventPageConditionChunk::Flags(x) => {
                                                             format!("Flags: {}", x.0)
@@ -160,13 +162,29 @@ pub fn update(
                                                 for (index, chunk) in
                                                     chunks.inner_vec.iter().enumerate()
                                                 {
-                                                    let node = (node << 8) + index as u64;
+                                                    let node = ids::field(node, index);
                                                     let label = match &chunk.data {
                                                         EventMoveRouteChunk::CommandsSize(val) => {
                                                             format!("CommandsSize: {}", val.0)
                                                         }
-                                                        EventMoveRouteChunk::Commands(val) => {
-                                                            format!("Commands: {:?}", val)
+                                                        EventMoveRouteChunk::Commands(bytes) => {
+                                                            if builder.dir(node, "Commands") {
+                                                                for (index, command) in
+                                                                    crate::move_route::decode(bytes)
+                                                                        .iter()
+                                                                        .enumerate()
+                                                                {
+                                                                    builder.leaf(
+                                                                        ids::command(node, index),
+                                                                        format!(
+                                                                            "{index}: {}",
+                                                                            command.label(encoding)
+                                                                        ),
+                                                                    );
+                                                                }
+                                                            }
+                                                            builder.close_dir();
+                                                            continue;
                                                         }
                                                         EventMoveRouteChunk::Repeat(val) => {
                                                             format!("Repeat: {}", val.0)
@@ -186,14 +204,8 @@ pub fn update(
                                             }
                                             EventPageChunk::Trigger(val) => format!(
                                                 "Trigger: {}",
-                                                match val.0 {
-                                                    0 => "Action Button",
-                                                    1 => "Player Touch",
-                                                    2 => "Event Touch",
-                                                    3 => "Autorun",
-                                                    4 => "Parallel process",
-                                                    _ => "Unknown",
-                                                }
+                                                Trigger::try_from(val.0)
+                                                    .map_or("Unknown", crate::labels::trigger)
                                             ),
                                             EventPageChunk::Priority(val) => format!(
                                                 "Priority: {}",
@@ -231,7 +243,7 @@ pub fn update(
                                                     commands.0.iter().enumerate()
                                                 {
                                                     builder.leaf(
-                                                        (node << 8) + index as u64,
+                                                        ids::command(node, index),
                                                         format!(
                                                             "{index}: {}{:?} {}",
                                                             "\t".repeat(command.indent as usize),
@@ -1,4 +1,7 @@
 pub mod database;
+pub mod fields;
+pub mod ids;
 pub mod map_tree;
 pub mod map_unit;
+pub mod offsets;
 pub mod save_data;
@@ -0,0 +1,705 @@
+//! Where the nodes of the raw views were read from, walking a file's chunks the way lcf does and
+//! numbering them with the views' [`ids`].
+
+use std::{collections::HashMap, io::Cursor, ops::Range};
+
+use binrw::BinRead;
+use lcf::{
+    helpers::Number,
+    raw::{
+        RawLcf,
+        ldb::LcfDataBaseChunk,
+        lmu::{LcfMapUnitChunk, event::command::Command},
+        lsd::LcfSaveDataChunk,
+    },
+};
+
+use crate::{
+    fields::{Field, Kind, database, save_data},
+    terms,
+    views::raw::ids::{self, child},
+};
+
+/// A tree node and the bytes it was read from.
+pub struct Span {
+    pub node: u64,
+    /// Index of the span of the directory holding the node.
+    pub parent: Option<usize>,
+    pub range: Range<usize>,
+    /// Whether the nodes inside this one are left out, as walking its bytes didn't end where lcf
+    /// says the chunk ends.
+    pub unlinked: bool,
+}
+
+/// Node ids leading to the span at `index`, for revealing it in the tree.
+pub fn path(spans: &[Span], index: usize) -> Vec<u64> {
+    let mut path = Vec::new();
+    let mut next = Some(index);
+    while let Some(index) = next {
+        path.push(spans[index].node);
+        next = spans[index].parent;
+    }
+    path.reverse();
+    path
+}
+
+/// The innermost span holding the byte at `offset`.
+pub fn find(spans: &[Span], offset: usize) -> Option<usize> {
+    spans
+        .iter()
+        .enumerate()
+        .filter(|(_, span)| span.range.contains(&offset))
+        .min_by_key(|(_, span)| span.range.len())
+        .map(|(index, _)| index)
+}
+
+/// What a top level chunk holds, as far as the tree goes.
+enum Shape {
+    Leaf,
+    Struct(&'static [Field]),
+    Records(&'static [Field]),
+    /// Records lcf reads as one list of chunks, which the view shows as records when they parse.
+    ChunkRecords(&'static [Field]),
+    System,
+    ChipSets,
+    Terms,
+    /// Switches, variables and common events.
+    Named,
+    Events,
+}
+
+/// Byte ranges of the nodes of the raw view of `raw`, which was read from `bytes`.
+///
+/// Nodes the views make up, like the chipset preview or the categories of terms, have no bytes
+/// of their own.
+pub fn spans(bytes: &[u8], raw: &RawLcf) -> Vec<Span> {
+    let shapes: Vec<_> = match raw {
+        RawLcf::RawDataBase(database) => database
+            .0
+            .inner_vec
+            .iter()
+            .map(|chunk| match &chunk.data {
+                LcfDataBaseChunk::Actors(_) => Shape::Records(database::ACTOR),
+                LcfDataBaseChunk::Skills(_) => Shape::Records(database::SKILL),
+                LcfDataBaseChunk::Items(_) => Shape::Records(database::ITEM),
+                LcfDataBaseChunk::Enemies(_) => Shape::Records(database::ENEMY),
+                LcfDataBaseChunk::Troops(_) => Shape::ChunkRecords(database::TROOP),
+                LcfDataBaseChunk::Terrain(_) => Shape::Records(database::TERRAIN),
+                LcfDataBaseChunk::Attributes(_) => Shape::ChunkRecords(database::ATTRIBUTE),
+                LcfDataBaseChunk::States(_) => Shape::ChunkRecords(database::STATE),
+                LcfDataBaseChunk::Animations(_) => Shape::Records(database::ANIMATION),
+                LcfDataBaseChunk::ChipSet(_) => Shape::ChipSets,
+                LcfDataBaseChunk::Terms(_) => Shape::Terms,
+                LcfDataBaseChunk::System(_) => Shape::Struct(database::SYSTEM),
+                LcfDataBaseChunk::Switches(_)
+                | LcfDataBaseChunk::Variables(_)
+                | LcfDataBaseChunk::CommonEvents(_) => Shape::Named,
+                _ => Shape::Leaf,
+            })
+            .collect(),
+        RawLcf::RawMapUnit(map_unit) => map_unit
+            .0
+            .inner_vec
+            .iter()
+            .map(|chunk| match &chunk.data {
+                LcfMapUnitChunk::Events(_) => Shape::Events,
+                _ => Shape::Leaf,
+            })
+            .collect(),
+        RawLcf::RawSaveData(save_data) => save_data
+            .0
+            .inner_vec
+            .iter()
+            .map(|chunk| match &chunk.data {
+                LcfSaveDataChunk::Title { .. } => Shape::Struct(save_data::TITLE),
+                LcfSaveDataChunk::System(_) => Shape::System,
+                LcfSaveDataChunk::Screen { .. } => Shape::Struct(save_data::SCREEN),
+                LcfSaveDataChunk::Pictures { .. } => Shape::Records(save_data::PICTURE),
+                LcfSaveDataChunk::PartyLocation { .. } => Shape::Struct(save_data::PARTY_LOCATION),
+                LcfSaveDataChunk::BoatLocation { .. }
+                | LcfSaveDataChunk::ShipLocation { .. }
+                | LcfSaveDataChunk::AirshipLocation { .. } => {
+                    Shape::Struct(save_data::VEHICLE_LOCATION)
+                }
+                LcfSaveDataChunk::Actors { .. } => Shape::Records(save_data::ACTOR),
+                LcfSaveDataChunk::Inventory { .. } => Shape::Struct(save_data::INVENTORY),
+                LcfSaveDataChunk::Targets { .. } => Shape::Records(save_data::TARGET),
+                LcfSaveDataChunk::MapInfo { .. } => Shape::Struct(save_data::MAP_INFO),
+                LcfSaveDataChunk::Panorama { .. } => Shape::Struct(save_data::PANORAMA),
+                LcfSaveDataChunk::ExecutionState { .. } => {
+                    Shape::Struct(save_data::EXECUTION_STATE)
+                }
+                LcfSaveDataChunk::CommonEvents { .. } => Shape::Records(save_data::COMMON_EVENT),
+                LcfSaveDataChunk::EasyRPG { .. } => Shape::Struct(save_data::EASY_RPG),
+                LcfSaveDataChunk::Unknown { .. } => Shape::Leaf,
+            })
+            .collect(),
+        RawLcf::RawMapTree(_) => Vec::new(),
+    };
+
+    let mut spans = Vec::new();
+    let mut reader = Reader { bytes, position: 0 };
+    // The header, like `LcfMapUnit`, is a string before the first chunk.
+    let Some(header) = reader.number() else {
+        return spans;
+    };
+    reader.position += header as usize;
+
+    if let RawLcf::RawMapTree(_) = raw {
+        walk_map_tree(bytes, reader.position, &mut spans);
+        return spans;
+    }
+
+    for (index, shape) in shapes.iter().enumerate() {
+        let start = reader.position;
+        let Some((_, data)) = reader.chunk() else {
+            break;
+        };
+        let node = index as u64;
+        let chunk = push(&mut spans, node, None, start..reader.position);
+        let (parent, first) = (Some(chunk), spans.len());
+        let end = match shape {
+            Shape::Leaf => continue,
+            Shape::Struct(fields) => {
+                walk_fields(bytes, data.clone(), node, parent, fields, &mut spans)
+            }
+            Shape::Records(fields) => {
+                walk_records(bytes, data.clone(), node, parent, fields, &mut spans)
+            }
+            Shape::ChunkRecords(fields) => {
+                if crate::fields::records(&bytes[data.clone()]).is_some() {
+                    walk_records(bytes, data.clone(), node, parent, fields, &mut spans)
+                } else {
+                    walk_fields(bytes, data.clone(), node, parent, fields, &mut spans)
+                }
+            }
+            Shape::System => walk_system(bytes, data.clone(), node, parent, &mut spans),
+            Shape::ChipSets => walk_chipsets(bytes, data.clone(), node, parent, &mut spans),
+            Shape::Terms => walk_terms(bytes, data.clone(), node, parent, &mut spans),
+            Shape::Named => walk_named(bytes, data.clone(), node, parent, &mut spans),
+            Shape::Events => walk_events(bytes, data.clone(), node, parent, &mut spans),
+        };
+        // Walking the bytes here can read them differently than lcf did. When it doesn't end
+        // where the chunk does, its nodes would point at the wrong bytes, so they are left out.
+        if end != data.end {
+            spans.truncate(first);
+            spans[chunk].unlinked = true;
+        }
+    }
+    spans
+}
+
+fn push(spans: &mut Vec<Span>, node: u64, parent: Option<usize>, range: Range<usize>) -> usize {
+    spans.push(Span {
+        node,
+        parent,
+        range,
+        unlinked: false,
+    });
+    spans.len() - 1
+}
+
+/// Walks the chunks of one record like `draw_fields` numbers them, returning where they end.
+fn walk_fields(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: Option<usize>,
+    fields: &'static [Field],
+    spans: &mut Vec<Span>,
+) -> usize {
+    let mut reader = Reader::new(bytes, &data);
+    for index in 1.. {
+        let start = reader.position;
+        let Some((id, data)) = reader.chunk() else {
+            // The terminator, which the view shows as "Null terminated".
+            if reader.position > start {
+                push(spans, child(node, 0), parent, start..reader.position);
+            }
+            break;
+        };
+        let node = child(node, index);
+        let parent = Some(push(spans, node, parent, start..reader.position));
+        match crate::fields::find(fields, id).map(|field| field.kind) {
+            Some(Kind::Struct(fields)) => {
+                walk_fields(bytes, data, node, parent, fields, spans);
+            }
+            Some(Kind::Records(fields)) => {
+                walk_records(bytes, data, node, parent, fields, spans);
+            }
+            _ => (),
+        }
+    }
+    reader.position
+}
+
+/// Walks a count followed by that many records, each an id and its chunks.
+fn walk_records(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: Option<usize>,
+    fields: &'static [Field],
+    spans: &mut Vec<Span>,
+) -> usize {
+    walk_array2d(
+        bytes,
+        data,
+        parent,
+        spans,
+        |index| child(node, index),
+        |data, node, parent, spans| walk_fields(bytes, data, node, Some(parent), fields, spans),
+    )
+}
+
+/// Walks a count followed by that many records, each an id and the chunks `chunks` walks,
+/// numbering the records with `number`. Returns where the records end.
+fn walk_array2d(
+    bytes: &[u8],
+    data: Range<usize>,
+    parent: Option<usize>,
+    spans: &mut Vec<Span>,
+    number: impl Fn(usize) -> u64,
+    mut chunks: impl FnMut(Range<usize>, u64, usize, &mut Vec<Span>) -> usize,
+) -> usize {
+    let mut reader = Reader::new(bytes, &data);
+    let Some(count) = reader.number() else {
+        return reader.position;
+    };
+    for index in 0..count as usize {
+        let start = reader.position;
+        if reader.number().is_none() {
+            break;
+        }
+        let node = number(index);
+        let record = push(spans, node, parent, start..start);
+        reader.position = chunks(reader.position..data.end, node, record, spans);
+        spans[record].range.end = reader.position;
+    }
+    reader.position
+}
+
+/// Walks an array of chunks numbered with `number`, letting `nested` walk the data of each.
+/// Returns where the array ends.
+fn walk_chunks(
+    bytes: &[u8],
+    data: Range<usize>,
+    parent: Option<usize>,
+    spans: &mut Vec<Span>,
+    number: impl Fn(usize) -> u64,
+    mut nested: impl FnMut(u32, Range<usize>, u64, usize, &mut Vec<Span>),
+) -> usize {
+    let mut reader = Reader::new(bytes, &data);
+    for index in 0.. {
+        let start = reader.position;
+        let Some((id, data)) = reader.chunk() else {
+            break;
+        };
+        let node = number(index);
+        let span = push(spans, node, parent, start..reader.position);
+        nested(id, data, node, span, spans);
+    }
+    reader.position
+}
+
+/// Walks the switches and variables of a save, numbered like the save view numbers them.
+fn walk_system(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: Option<usize>,
+    spans: &mut Vec<Span>,
+) -> usize {
+    walk_chunks(
+        bytes,
+        data,
+        parent,
+        spans,
+        |index| ids::field(node, index),
+        |id, data, node, parent, spans| {
+            // Switches are numbers, variables are 4 byte integers.
+            let width = match id {
+                32 => None,
+                34 => Some(4),
+                _ => return,
+            };
+            walk_values(
+                bytes,
+                data,
+                |index| ids::value(node, index),
+                parent,
+                width,
+                spans,
+            );
+        },
+    )
+}
+
+/// Walks a list of numbers, or of integers `width` bytes wide, numbered with `number`.
+fn walk_values(
+    bytes: &[u8],
+    data: Range<usize>,
+    number: impl Fn(usize) -> u64,
+    parent: usize,
+    width: Option<usize>,
+    spans: &mut Vec<Span>,
+) {
+    let mut reader = Reader::new(bytes, &data);
+    for index in 0.. {
+        let start = reader.position;
+        let read = match width {
+            None => reader.number().is_some(),
+            Some(width) if start + width <= data.end => {
+                reader.position += width;
+                true
+            }
+            Some(_) => false,
+        };
+        if !read {
+            break;
+        }
+        push(spans, number(index), Some(parent), start..reader.position);
+    }
+}
+
+/// Walks the chipsets of a database, with the tiles of their terrain and passability tables.
+fn walk_chipsets(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: Option<usize>,
+    spans: &mut Vec<Span>,
+) -> usize {
+    walk_array2d(
+        bytes,
+        data,
+        parent,
+        spans,
+        |index| ids::chipset(node, index),
+        |data, node, parent, spans| {
+            walk_chunks(
+                bytes,
+                data,
+                Some(parent),
+                spans,
+                |index| ids::field(node, index),
+                |id, data, node, parent, spans| {
+                    // Terrain is 2 byte integers, passability numbers.
+                    let width = match id {
+                        3 => Some(2),
+                        4 | 5 => None,
+                        _ => return,
+                    };
+                    let number = |index| ids::value(node, index);
+                    walk_values(bytes, data, number, parent, width, spans);
+                },
+            )
+        },
+    )
+}
+
+/// Walks the terms of a database, each under the category the view files it in.
+fn walk_terms(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: Option<usize>,
+    spans: &mut Vec<Span>,
+) -> usize {
+    let mut reader = Reader::new(bytes, &data);
+    // Categories hold terms from all over the chunk, so they have no bytes of their own.
+    let mut categories = HashMap::new();
+    for index in 0.. {
+        let start = reader.position;
+        let Some((id, _)) = reader.chunk() else {
+            if reader.position > start {
+                push(
+                    spans,
+                    ids::terms_terminator(node),
+                    parent,
+                    start..reader.position,
+                );
+            }
+            break;
+        };
+        let category = terms::CATEGORIES
+            .iter()
+            .position(|category| *category == terms::Category::of(id));
+        let Some(category) = category else {
+            continue;
+        };
+        let category = *categories.entry(category).or_insert_with(|| {
+            push(
+                spans,
+                ids::term_category(node, category),
+                parent,
+                start..start,
+            )
+        });
+        push(
+            spans,
+            ids::term(node, index),
+            Some(category),
+            start..reader.position,
+        );
+    }
+    reader.position
+}
+
+/// Walks switch and variable names, or common events.
+fn walk_named(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: Option<usize>,
+    spans: &mut Vec<Span>,
+) -> usize {
+    walk_array2d(
+        bytes,
+        data,
+        parent,
+        spans,
+        |index| ids::named(node, index),
+        |data, node, parent, spans| {
+            walk_chunks(
+                bytes,
+                data,
+                Some(parent),
+                spans,
+                |index| ids::named_field(node, index),
+                |id, data, node, parent, spans| {
+                    // The commands of a common event.
+                    if id == 22 {
+                        walk_commands(bytes, data, node, parent, spans);
+                    }
+                },
+            )
+        },
+    )
+}
+
+/// Walks the events of a map.
+fn walk_events(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: Option<usize>,
+    spans: &mut Vec<Span>,
+) -> usize {
+    walk_array2d(
+        bytes,
+        data,
+        parent,
+        spans,
+        |index| ids::event(node, index),
+        |data, node, parent, spans| {
+            walk_chunks(
+                bytes,
+                data,
+                Some(parent),
+                spans,
+                |index| ids::event_field(node, index),
+                |id, data, node, parent, spans| {
+                    if id == 5 {
+                        walk_pages(bytes, data, node, parent, spans);
+                    }
+                },
+            )
+        },
+    )
+}
+
+/// Walks the pages of an event.
+fn walk_pages(bytes: &[u8], data: Range<usize>, node: u64, parent: usize, spans: &mut Vec<Span>) {
+    walk_array2d(
+        bytes,
+        data,
+        Some(parent),
+        spans,
+        |index| ids::page(node, index),
+        |data, page, parent, spans| {
+            walk_chunks(
+                bytes,
+                data,
+                Some(parent),
+                spans,
+                |index| ids::page_field(page, index),
+                |id, data, node, parent, spans| match id {
+                    // The condition and the move route.
+                    2 | 41 => {
+                        walk_chunks(
+                            bytes,
+                            data,
+                            Some(parent),
+                            spans,
+                            |index| ids::field(node, index),
+                            |id, data, node, parent, spans| {
+                                if id == 12 {
+                                    walk_move_route(bytes, data, node, parent, spans);
+                                }
+                            },
+                        );
+                    }
+                    52 => walk_commands(bytes, data, node, parent, spans),
+                    _ => (),
+                },
+            )
+        },
+    );
+}
+
+/// Walks event commands with lcf up to the zero that ends them.
+fn walk_commands(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: usize,
+    spans: &mut Vec<Span>,
+) {
+    let mut cursor = Cursor::new(&bytes[data.clone()]);
+    for index in 0.. {
+        let start = cursor.position() as usize;
+        let rest = &bytes[data.start + start..data.end];
+        if rest.len() < 4 || rest[..4] == [0; 4] {
+            break;
+        }
+        if Command::read_le(&mut cursor).is_err() {
+            break;
+        }
+        let range = data.start + start..data.start + cursor.position() as usize;
+        push(spans, ids::command(node, index), Some(parent), range);
+    }
+}
+
+fn walk_move_route(
+    bytes: &[u8],
+    data: Range<usize>,
+    node: u64,
+    parent: usize,
+    spans: &mut Vec<Span>,
+) {
+    let commands = crate::move_route::decode_ranges(&bytes[data.clone()]);
+    for (index, (_, range)) in commands.into_iter().enumerate() {
+        let range = data.start + range.start..data.start + range.end;
+        push(spans, ids::command(node, index), Some(parent), range);
+    }
+}
+
+/// Walks a map tree after its header: its maps, their order, the active map and where the game
+/// starts.
+fn walk_map_tree(bytes: &[u8], start: usize, spans: &mut Vec<Span>) {
+    let maps = push(spans, ids::MAPS, None, start..start);
+    let end = walk_array2d(
+        bytes,
+        start..bytes.len(),
+        Some(maps),
+        spans,
+        ids::map,
+        |data, node, parent, spans| {
+            walk_chunks(
+                bytes,
+                data,
+                Some(parent),
+                spans,
+                |index| ids::field(node, index),
+                |id, data, node, parent, spans| match id {
+                    // The bounds of an area, four 4 byte integers.
+                    51 => {
+                        let number = |index| ids::area_bound(node, index);
+                        walk_values(bytes, data, number, parent, Some(4), spans);
+                    }
+                    12 => {
+                        walk_chunks(
+                            bytes,
+                            data,
+                            Some(parent),
+                            spans,
+                            |index| ids::bgm_field(node, index),
+                            |_, _, _, _, _| (),
+                        );
+                    }
+                    _ => (),
+                },
+            )
+        },
+    );
+    spans[maps].range.end = end;
+
+    let mut reader = Reader {
+        bytes,
+        position: end,
+    };
+    let Some(count) = reader.number() else {
+        return;
+    };
+    let order = push(spans, ids::ORDER, None, end..end);
+    for index in 0..count as usize {
+        let start = reader.position;
+        if reader.number().is_none() {
+            return;
+        }
+        push(
+            spans,
+            ids::order_entry(index),
+            Some(order),
+            start..reader.position,
+        );
+    }
+    spans[order].range.end = reader.position;
+
+    let start = reader.position;
+    if reader.number().is_none() {
+        return;
+    }
+    push(spans, ids::ACTIVE, None, start..reader.position);
+
+    let start = reader.position;
+    let party = push(spans, ids::START, None, start..start);
+    let end = walk_chunks(
+        bytes,
+        start..bytes.len(),
+        Some(party),
+        spans,
+        ids::start_field,
+        |_, _, _, _, _| (),
+    );
+    spans[party].range.end = end;
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Reads `data` of `bytes`, never past its end.
+    fn new(bytes: &'a [u8], data: &Range<usize>) -> Self {
+        Self {
+            bytes: &bytes[..data.end],
+            position: data.start,
+        }
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        let mut cursor = Cursor::new(self.bytes.get(self.position..)?);
+        let number = Number::read_le(&mut cursor).ok()?.0;
+        self.position += cursor.position() as usize;
+        Some(number)
+    }
+
+    /// Reads the id and length of a chunk, returning the id and the range of its data. At the
+    /// end of the array, skips the zero ending it, if any, and returns `None`.
+    fn chunk(&mut self) -> Option<(u32, Range<usize>)> {
+        if *self.bytes.get(self.position)? == 0 {
+            self.position += 1;
+            return None;
+        }
+        let start = self.position;
+        let chunk = self.number().zip(self.number()).and_then(|(id, length)| {
+            let data = self.position..self.position.checked_add(length as usize)?;
+            (data.end <= self.bytes.len()).then_some((id, data))
+        });
+        self.position = chunk.as_ref().map_or(start, |(_, data)| data.end);
+        chunk
+    }
+}
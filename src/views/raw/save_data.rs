@@ -1,30 +1,80 @@
+use std::collections::HashMap;
+
+use egui_ltreeview::NodeBuilder;
 use lcf::raw::lsd::{LcfSaveDataChunk, RawLcfSaveData, SaveSystemChunk};
 
+use crate::{
+    fields::save_data,
+    project::Project,
+    views::raw::{
+        fields::{draw_bytes, draw_struct},
+        ids,
+    },
+};
+
+/// What the switch and variable lists of a save leave out.
+#[derive(Default)]
+pub struct Filters {
+    pub only_on: bool,
+    pub non_zero: bool,
+}
+
 pub fn update(
     save_data: &RawLcfSaveData,
     builder: &mut egui_ltreeview::TreeViewBuilder<'_, u64>,
-    _encoding: crate::code_page::CodePage,
+    encoding: crate::code_page::CodePage,
+    project: Option<&Project>,
+    filters: &mut Filters,
 ) {
+    let switches: HashMap<_, _> = project
+        .map_or_default(|project| project.switch_names())
+        .into_iter()
+        .collect();
+    let variables: HashMap<_, _> = project
+        .map_or_default(|project| project.variable_names())
+        .into_iter()
+        .collect();
+
     for (index, chunk) in save_data.0.inner_vec.iter().enumerate() {
         match &chunk.data {
-            LcfSaveDataChunk::Title { bytes } => {
-                builder.leaf(index as u64, format!("Title: {bytes:?}"))
-            }
+            LcfSaveDataChunk::Title { bytes } => draw_struct(
+                index as u64,
+                "Title",
+                bytes,
+                save_data::TITLE,
+                builder,
+                encoding,
+            ),
             LcfSaveDataChunk::System(chunks) => {
                 if builder.dir(index as u64, "System") {
-                    let node = (index as u64) << 8;
+                    let system = index as u64;
                     for (index, chunk) in chunks.iter().enumerate() {
-                        let node = node + index as u64;
+                        let node = ids::field(system, index);
                         match &chunk.data {
                             SaveSystemChunk::SwitchesSize(val) => {
                                 builder.leaf(node, format!("Switches Size: {}", val.0))
                             }
                             SaveSystemChunk::Switches(numbers) => {
-                                if builder.dir(node, "Switches") {
+                                let label = if filters.only_on {
+                                    "Switches (only ON)"
+                                } else {
+                                    "Switches"
+                                };
+                                let dir = NodeBuilder::dir(node).label(label).context_menu(|ui| {
+                                    ui.checkbox(&mut filters.only_on, "Only ON");
+                                });
+                                if builder.node(dir) {
                                     for (index, number) in numbers.iter().enumerate() {
+                                        if filters.only_on && number.0 == 0 {
+                                            continue;
+                                        }
                                         builder.leaf(
-                                            (node << 16) + index as u64,
-                                            format!("{index}: {}", number.0 != 0),
+                                            ids::value(node, index),
+                                            format!(
+                                                "{}: {}",
+                                                entry("S", index, &switches, encoding),
+                                                number.0 != 0
+                                            ),
                                         );
                                     }
                                 }
@@ -34,11 +84,25 @@ pub fn update(
                                 builder.leaf(node, format!("Variables Size: {}", val.0))
                             }
                             SaveSystemChunk::Variables(numbers) => {
-                                if builder.dir(node, "Variables") {
+                                let label = if filters.non_zero {
+                                    "Variables (non-zero)"
+                                } else {
+                                    "Variables"
+                                };
+                                let dir = NodeBuilder::dir(node).label(label).context_menu(|ui| {
+                                    ui.checkbox(&mut filters.non_zero, "Only non-zero");
+                                });
+                                if builder.node(dir) {
                                     for (index, number) in numbers.iter().enumerate() {
+                                        if filters.non_zero && *number == 0 {
+                                            continue;
+                                        }
                                         builder.leaf(
-                                            (node << 16) + index as u64,
-                                            format!("{index}: {}", number),
+                                            ids::value(node, index),
+                                            format!(
+                                                "{}: {number}",
+                                                entry("V", index, &variables, encoding)
+                                            ),
                                         );
                                     }
                                 }
@@ -52,51 +116,133 @@ pub fn update(
                 }
                 builder.close_dir();
             }
-            LcfSaveDataChunk::Screen { bytes } => {
-                builder.leaf(index as u64, format!("Screen: {bytes:?}"))
-            }
-            LcfSaveDataChunk::Pictures { bytes } => {
-                builder.leaf(index as u64, format!("Pictures: {bytes:?}"))
-            }
-            LcfSaveDataChunk::PartyLocation { bytes } => {
-                builder.leaf(index as u64, format!("Party Location: {bytes:?}"))
-            }
-            LcfSaveDataChunk::BoatLocation { bytes } => {
-                builder.leaf(index as u64, format!("Boat Location: {bytes:?}"))
-            }
-            LcfSaveDataChunk::ShipLocation { bytes } => {
-                builder.leaf(index as u64, format!("Ship Location: {bytes:?}"))
-            }
-            LcfSaveDataChunk::AirshipLocation { bytes } => {
-                builder.leaf(index as u64, format!("Airship Location: {bytes:?}"))
-            }
-            LcfSaveDataChunk::Actors { bytes } => {
-                builder.leaf(index as u64, format!("Actors: {bytes:?}"))
-            }
-            LcfSaveDataChunk::Inventory { bytes } => {
-                builder.leaf(index as u64, format!("Inventory: {bytes:?}"))
-            }
-            LcfSaveDataChunk::Targets { bytes } => {
-                builder.leaf(index as u64, format!("Targets: {bytes:?}"))
-            }
-            LcfSaveDataChunk::MapInfo { bytes } => {
-                builder.leaf(index as u64, format!("Map Info: {bytes:?}"))
-            }
-            LcfSaveDataChunk::Panorama { bytes } => {
-                builder.leaf(index as u64, format!("Panorama: {bytes:?}"))
-            }
-            LcfSaveDataChunk::ExecutionState { bytes } => {
-                builder.leaf(index as u64, format!("Execution State: {bytes:?}"))
-            }
-            LcfSaveDataChunk::CommonEvents { bytes } => {
-                builder.leaf(index as u64, format!("Common Events: {bytes:?}"))
-            }
-            LcfSaveDataChunk::EasyRPG { bytes } => {
-                builder.leaf(index as u64, format!("EasyRPG: {bytes:?}"))
-            }
+            LcfSaveDataChunk::Screen { bytes } => draw_struct(
+                index as u64,
+                "Screen",
+                bytes,
+                save_data::SCREEN,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::Pictures { bytes } => draw_bytes(
+                index as u64,
+                "Pictures",
+                bytes,
+                save_data::PICTURE,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::PartyLocation { bytes } => draw_struct(
+                index as u64,
+                "Party Location",
+                bytes,
+                save_data::PARTY_LOCATION,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::BoatLocation { bytes } => draw_struct(
+                index as u64,
+                "Boat Location",
+                bytes,
+                save_data::VEHICLE_LOCATION,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::ShipLocation { bytes } => draw_struct(
+                index as u64,
+                "Ship Location",
+                bytes,
+                save_data::VEHICLE_LOCATION,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::AirshipLocation { bytes } => draw_struct(
+                index as u64,
+                "Airship Location",
+                bytes,
+                save_data::VEHICLE_LOCATION,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::Actors { bytes } => draw_bytes(
+                index as u64,
+                "Actors",
+                bytes,
+                save_data::ACTOR,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::Inventory { bytes } => draw_struct(
+                index as u64,
+                "Inventory",
+                bytes,
+                save_data::INVENTORY,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::Targets { bytes } => draw_bytes(
+                index as u64,
+                "Targets",
+                bytes,
+                save_data::TARGET,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::MapInfo { bytes } => draw_struct(
+                index as u64,
+                "Map Info",
+                bytes,
+                save_data::MAP_INFO,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::Panorama { bytes } => draw_struct(
+                index as u64,
+                "Panorama",
+                bytes,
+                save_data::PANORAMA,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::ExecutionState { bytes } => draw_struct(
+                index as u64,
+                "Execution State",
+                bytes,
+                save_data::EXECUTION_STATE,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::CommonEvents { bytes } => draw_bytes(
+                index as u64,
+                "Common Events",
+                bytes,
+                save_data::COMMON_EVENT,
+                builder,
+                encoding,
+            ),
+            LcfSaveDataChunk::EasyRPG { bytes } => draw_struct(
+                index as u64,
+                "EasyRPG",
+                bytes,
+                save_data::EASY_RPG,
+                builder,
+                encoding,
+            ),
             LcfSaveDataChunk::Unknown { id, bytes } => {
                 builder.leaf(index as u64, format!("Chunk {}: {bytes:?}", id))
             }
         };
     }
 }
+
+/// A switch or variable as the editor lists it, like `S0012 Boss Defeated`. Saves store them
+/// from index 0, while their ids start at 1.
+fn entry(
+    prefix: &str,
+    index: usize,
+    names: &HashMap<u32, &[u8]>,
+    encoding: crate::code_page::CodePage,
+) -> String {
+    let id = index as u32 + 1;
+    crate::labels::named(prefix, id, names.get(&id).copied(), encoding)
+}
@@ -0,0 +1,100 @@
+use lcf::lmu::event::page::Graphic;
+
+use crate::{assets::Assets, sheet};
+
+/// Draws the sprite of an event page at twice its size, with buttons to cycle through the
+/// other directions and patterns of the same character.
+///
+/// Pages without a charset show their upper layer tile from `chipset` instead.
+pub fn preview(
+    ui: &mut egui::Ui,
+    assets: &mut Assets,
+    graphic: &Graphic,
+    chipset: Option<&[u8]>,
+    encoding: crate::code_page::CodePage,
+    id: egui::Id,
+) {
+    let tint = if graphic.transparent {
+        egui::Color32::from_white_alpha(128)
+    } else {
+        egui::Color32::WHITE
+    };
+
+    if graphic.file.is_empty() {
+        let Some(texture) =
+            chipset.and_then(|file| assets.texture(ui.ctx(), "ChipSet", file, encoding))
+        else {
+            ui.label("Chipset not found");
+            return;
+        };
+        let (x, y) = sheet::upper_tile(graphic.index);
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(x as f32, y as f32),
+            egui::vec2(sheet::TILE as f32, sheet::TILE as f32),
+        );
+        ui.add(frame(&texture, rect, tint));
+        return;
+    }
+
+    let Some(texture) = assets.texture(ui.ctx(), "CharSet", &graphic.file, encoding) else {
+        ui.label("CharSet not found");
+        return;
+    };
+
+    let (mut direction, mut pattern) = ui.data(|data| {
+        data.get_temp(id)
+            .unwrap_or((graphic.direction as u32, graphic.pattern))
+    });
+    let (x, y) = sheet::charset_frame(graphic.index, direction, pattern);
+    let rect = egui::Rect::from_min_size(
+        egui::pos2(x as f32, y as f32),
+        egui::vec2(sheet::FRAME_WIDTH as f32, sheet::FRAME_HEIGHT as f32),
+    );
+
+    ui.horizontal(|ui| {
+        ui.add(frame(&texture, rect, tint));
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                if ui.small_button("◀").clicked() {
+                    direction = (direction + 3) % 4;
+                }
+                ui.label(match direction {
+                    0 => "Up",
+                    1 => "Right",
+                    2 => "Down",
+                    _ => "Left",
+                });
+                if ui.small_button("▶").clicked() {
+                    direction = (direction + 1) % 4;
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.small_button("◀").clicked() {
+                    pattern = (pattern + 2) % 3;
+                }
+                ui.label(format!("Pattern {pattern}"));
+                if ui.small_button("▶").clicked() {
+                    pattern = (pattern + 1) % 3;
+                }
+            });
+        });
+    });
+    ui.data_mut(|data| data.insert_temp(id, (direction, pattern)));
+}
+
+/// A part of a texture, given in pixels, shown at twice its size.
+fn frame(texture: &egui::TextureHandle, rect: egui::Rect, tint: egui::Color32) -> egui::Image<'_> {
+    egui::Image::new(texture)
+        .uv(uv(texture, rect))
+        .fit_to_exact_size(rect.size() * 2.0)
+        .tint(tint)
+}
+
+/// Texture coordinates of a part of a texture given in pixels.
+pub fn uv(texture: &egui::TextureHandle, rect: egui::Rect) -> egui::Rect {
+    let size = texture.size_vec2();
+    egui::Rect::from_min_max(
+        (rect.min.to_vec2() / size).to_pos2(),
+        (rect.max.to_vec2() / size).to_pos2(),
+    )
+}